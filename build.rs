@@ -6,18 +6,15 @@ fn main() {
     // Get the output directory from the environment variable
     let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
 
-    fs::copy("link.ld", out_dir.join("link.ld")).unwrap();
-    println!("carg:rustc-link-search={}", out_dir.display());
+    // Select the memory layout: RAM (debugger-loaded) or execute-in-place flash
+    let script = if env::var_os("CARGO_FEATURE_XIP_FLASH").is_some() {
+        "link-flash.ld"
+    } else {
+        "link.ld"
+    };
+    fs::copy(script, out_dir.join("link.ld")).unwrap();
+    println!("cargo:rustc-link-search={}", out_dir.display());
 
-    // Assemble boot code for cr52_rt
-    cc::Build::new()
-        .file("boot.s")
-        .flag("-march=armv8-r")
-        .flag("-mcpu=cortex-r52")
-        .flag("-mfpu=vfpv3-d16")
-        .flag("-mfloat-abi=hard")
-        .compile("boot");
-
-    println!("cargo:rerun-if-changed=boot.s");
     println!("cargo:rerun-if-changed=link.ld");
+    println!("cargo:rerun-if-changed=link-flash.ld");
 }
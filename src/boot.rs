@@ -0,0 +1,709 @@
+/// Reset and exception entry code
+///
+/// The vector table, `_reset` and the exception entry/exit trampolines are
+/// assembled from this file by rustc, so no external ARM toolchain is needed.
+/// Feature-dependent code is selected at compile time:
+/// * "fpu" (default) - the VFP is enabled at reset, and d0-d7 and FPSCR are
+///   saved around every handler, since the compiler uses those registers
+///   freely in handler code; required on hard-float targets
+/// * "fpu-flush-to-zero", "fpu-default-nan" - set FPSCR.FZ and FPSCR.DN at
+///   reset
+/// * "lazy-fpu-context" - the FPU is disabled instead of saved while a
+///   handler runs; for applications whose handlers never touch floating point
+/// * "nested-irq" - IRQ handler runs in SVC mode so it can be preempted
+/// * "xip-flash" - selects `link-flash.ld`: code runs from flash, and `.data`
+///   and `.ramfunc` are copied to RAM here
+/// * "tcm" - the ATCM/BTCM/CTCM are placed at the linker script's TCM
+///   regions, zero-filled and loaded with `.tcm_text`/`.tcm_data`
+///
+/// `_reset` initializes statics in Hyp mode and then calls `el2_init` on the
+/// Hyp stack before dropping to EL1. The default does nothing; define
+/// `#[unsafe(no_mangle)] extern "C" fn el2_init()` to configure the EL2 MPU
+/// (see [`HypMpu`](crate::mpu::HypMpu)) or HCR.
+///
+/// The trampolines call these symbols, which the application must define
+/// unless "default-handlers" provides them: `rust_irq_handler`,
+/// `rust_fiq_handler`, `rust_swi_handler`, `rust_undef_handler`,
+/// `rust_data_abort_handler`, `rust_prefetch_abort_handler`, and
+/// `rust_hyp_trap_handler` for exceptions taken to Hyp mode through the
+/// separate Hyp vector table.
+///
+/// Every trampoline aligns the stack to 8 bytes before calling into Rust.
+use core::arch::global_asm;
+
+use core::mem::{offset_of, size_of};
+
+use crate::exception::{ExceptionFrame, HypTrapFrame, SPSR_T_BIT};
+use crate::irq::{
+    ARM_MODE_ABT, ARM_MODE_FIQ, ARM_MODE_IRQ, ARM_MODE_SVC, ARM_MODE_SYS, ARM_MODE_UND,
+    ARM_MODE_USR, CPSR_F_BIT, CPSR_I_BIT,
+};
+
+#[cfg(all(feature = "fpu", not(target_abi = "eabihf")))]
+compile_error!("feature \"fpu\" requires a hard-float target such as armv8r-none-eabihf");
+
+#[cfg(all(not(feature = "fpu"), target_abi = "eabihf"))]
+compile_error!(
+    "hard-float targets emit VFP instructions, so the \"fpu\" feature must stay enabled; \
+     build with --no-default-features only for the soft-float target armv8r-none-eabi.json"
+);
+
+/// FPSCR value installed at reset
+#[cfg(feature = "fpu")]
+const FPSCR_INIT: u32 = {
+    let fz = if cfg!(feature = "fpu-flush-to-zero") {
+        1 << 24
+    } else {
+        0
+    };
+    let dn = if cfg!(feature = "fpu-default-nan") {
+        1 << 25
+    } else {
+        0
+    };
+    fz | dn
+};
+
+/// Call the FPU setup from `_reset`, while still in Hyp mode
+#[cfg(feature = "fpu")]
+macro_rules! fpu_init {
+    () => {
+        r#"
+    bl _fpu_init                  @ Enable the VFP before any Rust code runs
+"#
+    };
+}
+
+#[cfg(not(feature = "fpu"))]
+macro_rules! fpu_init {
+    () => {
+        ""
+    };
+}
+
+// Enable EL1 and Hyp access to the VFP (HCPTR, CPACR), switch it on
+// (FPEXC.EN) and install the default FPSCR. Runs without a stack.
+#[cfg(feature = "fpu")]
+global_asm!(
+    r#"
+    .syntax unified
+    .arm
+
+.section .boot, "ax", %progbits
+.align 2
+.global _fpu_init
+
+_fpu_init:
+    mrc p15, 4, r0, c1, c1, 2     @ HCPTR
+    bic r0, r0, #0xC00            @ Clear TCP10/TCP11: do not trap cp10/cp11
+    mcr p15, 4, r0, c1, c1, 2
+    mrc p15, 0, r0, c1, c0, 2     @ CPACR
+    orr r0, r0, #0x00F00000       @ Full access to cp10/cp11
+    mcr p15, 0, r0, c1, c0, 2
+    isb
+    mov r0, #0x40000000           @ FPEXC.EN
+    vmsr fpexc, r0
+    ldr r0, ={fpscr}
+    vmsr fpscr, r0
+    bx lr
+"#,
+    fpscr = const FPSCR_INIT,
+);
+
+/// Call the TCM setup from `_reset`, while still in Hyp mode
+#[cfg(feature = "tcm")]
+macro_rules! tcm_init {
+    () => {
+        r#"
+    bl _tcm_init                  @ Place, enable and zero-fill the TCMs
+"#
+    };
+}
+
+#[cfg(not(feature = "tcm"))]
+macro_rules! tcm_init {
+    () => {
+        ""
+    };
+}
+
+/// Copy `.tcm_text` and `.tcm_data` from their load addresses
+#[cfg(feature = "tcm")]
+macro_rules! tcm_copy {
+    () => {
+        r#"
+    ldr r0, =_stcm_text
+    ldr r1, =_etcm_text
+    ldr r2, =_sitcm_text
+    bl copy_section
+    ldr r0, =_stcm_data
+    ldr r1, =_etcm_data
+    ldr r2, =_sitcm_data
+    bl copy_section
+"#
+    };
+}
+
+#[cfg(not(feature = "tcm"))]
+macro_rules! tcm_copy {
+    () => {
+        ""
+    };
+}
+
+// Program IMP_{A,B,C}TCMREGIONR with the linker script's TCM bases, enable
+// each TCM at EL2 and EL1/EL0, and zero-fill it. With ECC, a TCM read of a
+// location that was never written reports an error, so every 64-bit ECC
+// granule is written here with a doubleword store. The fill covers the size
+// the register reports back, not the linker LENGTH, and a TCM reporting size
+// 0 (not implemented) is skipped. Only writable at EL2; runs without a stack.
+#[cfg(feature = "tcm")]
+global_asm!(
+    r#"
+    .syntax unified
+    .arm
+
+.macro tcm_enable_and_zero opc2, base
+    ldr r0, =\base
+    orr r0, r0, #{enable}         @ EnableEL2 | EnableEL10
+    mcr p15, 0, r0, c9, c1, \opc2 @ IMP_xTCMREGIONR
+    isb
+    mrc p15, 0, r1, c9, c1, \opc2 @ Read back the implemented Size [6:2]
+    ubfx r2, r1, #{size_shift}, #5
+    cmp r2, #0
+    beq 2f                        @ TCM not present
+    mov r3, #512
+    lsl r3, r3, r2                @ Size in bytes: 512 << Size
+    lsr r0, r1, #13
+    lsl r0, r0, #13               @ BaseAddress [31:13]
+    add r1, r0, r3
+    mov r2, #0
+    mov r3, #0
+1:
+    strd r2, r3, [r0], #8
+    cmp r0, r1
+    blo 1b
+2:
+.endm
+
+.section .boot, "ax", %progbits
+.align 2
+.global _tcm_init
+
+_tcm_init:
+    tcm_enable_and_zero 0, __atcm_start
+    tcm_enable_and_zero 1, __btcm_start
+    tcm_enable_and_zero 2, __ctcm_start
+    dsb sy
+    bx lr
+"#,
+    enable = const crate::tcm::TCM_ENABLE_EL2 | crate::tcm::TCM_ENABLE_EL10,
+    size_shift = const crate::tcm::TCM_SIZE_SHIFT,
+);
+
+/// Save the caller-saved VFP state (d0-d7, FPSCR) before calling into Rust
+#[cfg(all(feature = "fpu", not(feature = "lazy-fpu-context")))]
+macro_rules! fpu_save {
+    () => {
+        r#"
+    vpush {{d0-d7}}              @ Caller-saved VFP registers
+    vmrs r1, fpscr
+    push {{r1, r2}}              @ FPSCR (r2 keeps the stack 8-byte aligned)
+"#
+    };
+}
+
+/// Restore the VFP state saved by `fpu_save!`
+#[cfg(all(feature = "fpu", not(feature = "lazy-fpu-context")))]
+macro_rules! fpu_restore {
+    () => {
+        r#"
+    pop {{r1, r2}}
+    vmsr fpscr, r1
+    vpop {{d0-d7}}
+"#
+    };
+}
+
+/// Lazy mode: the handler promises not to use the FPU, so it is disabled
+/// (FPEXC.EN cleared) instead of saved; any VFP instruction traps as undefined
+#[cfg(feature = "lazy-fpu-context")]
+macro_rules! fpu_save {
+    () => {
+        r#"
+    vmrs r1, fpexc
+    push {{r1, r2}}              @ FPEXC (r2 keeps the stack 8-byte aligned)
+    bic r1, r1, #0x40000000      @ Clear FPEXC.EN
+    vmsr fpexc, r1
+"#
+    };
+}
+
+/// Re-enable the FPU as it was before `fpu_save!`
+#[cfg(feature = "lazy-fpu-context")]
+macro_rules! fpu_restore {
+    () => {
+        r#"
+    pop {{r1, r2}}
+    vmsr fpexc, r1
+"#
+    };
+}
+
+#[cfg(not(feature = "fpu"))]
+macro_rules! fpu_save {
+    () => {
+        ""
+    };
+}
+
+#[cfg(not(feature = "fpu"))]
+macro_rules! fpu_restore {
+    () => {
+        ""
+    };
+}
+
+// Hyp vector table, installed in HVBAR. Every entry builds a HypTrapFrame
+// (vector offset, HSR/HDFAR/HIFAR/HPFAR, ELR_hyp, SPSR_hyp, r0-r12, LR) on
+// the Hyp stack, calls rust_hyp_trap_handler with its address and returns
+// with eret. Hyp mode has no banked LR, so LR_usr is saved as well. The 0x14
+// Hyp Trap entry receives exceptions routed from EL1/EL0, including EL2 MPU
+// faults once HypMpu::enable_for_el1 has been called.
+global_asm!(
+    r#"
+    .syntax unified
+    .arm
+
+.section .text._hyp_vector_table, "ax", %progbits
+.align 5
+.global _hyp_vector_table
+
+_hyp_vector_table:
+    b _reset                     /* 0x00: Reset */
+    b hyp_undef_asm              /* 0x04: Undefined Instruction in Hyp mode */
+    b hyp_hvc_asm                /* 0x08: Hypervisor Call */
+    b hyp_prefetch_abort_asm     /* 0x0C: Prefetch Abort in Hyp mode */
+    b hyp_data_abort_asm         /* 0x10: Data Abort in Hyp mode */
+    b hyp_trap_asm               /* 0x14: Hyp Trap (from EL1/EL0) */
+    b hyp_irq_asm                /* 0x18: IRQ */
+    b hyp_fiq_asm                /* 0x1C: FIQ */
+
+hyp_undef_asm:
+    push {{r0-r12, lr}}
+    mov r0, #0x04                @ Vector offset
+    b hyp_exception_common
+
+hyp_hvc_asm:
+    push {{r0-r12, lr}}
+    mov r0, #0x08
+    b hyp_exception_common
+
+hyp_prefetch_abort_asm:
+    push {{r0-r12, lr}}
+    mov r0, #0x0C
+    b hyp_exception_common
+
+hyp_data_abort_asm:
+    push {{r0-r12, lr}}
+    mov r0, #0x10
+    b hyp_exception_common
+
+hyp_trap_asm:
+    push {{r0-r12, lr}}
+    mov r0, #0x14
+    b hyp_exception_common
+
+hyp_irq_asm:
+    push {{r0-r12, lr}}
+    mov r0, #0x18
+    b hyp_exception_common
+
+hyp_fiq_asm:
+    push {{r0-r12, lr}}
+    mov r0, #0x1C
+    b hyp_exception_common
+
+hyp_exception_common:
+    mrs r1, elr_hyp
+    mrs r2, spsr
+    push {{r1, r2}}              @ Return address and SPSR_hyp
+    mrc p15, 4, r1, c5, c2, 0    @ HSR
+    mrc p15, 4, r2, c6, c0, 0    @ HDFAR
+    mrc p15, 4, r3, c6, c0, 2    @ HIFAR
+    mrc p15, 4, r12, c6, c0, 4   @ HPFAR
+    push {{r0-r3, r12}}          @ Vector offset and syndrome
+    mov r4, sp                   @ HypTrapFrame address
+    bic sp, sp, #7               @ Align the stack to 8 bytes for AAPCS
+"#,
+    fpu_save!(),
+    r#"
+    mov r0, r4                   @ Pass the HypTrapFrame to Rust
+    bl rust_hyp_trap_handler
+"#,
+    fpu_restore!(),
+    r#"
+    add sp, r4, #{info_size}     @ Drop the vector offset and syndrome
+    pop {{r1, r2}}
+    msr elr_hyp, r1
+    msr spsr_fsxc, r2
+    pop {{r0-r12, lr}}
+    eret
+"#,
+    info_size = const offset_of!(HypTrapFrame, elr),
+);
+
+/// IRQ entry: the handler runs in IRQ mode with IRQs masked
+#[cfg(not(feature = "nested-irq"))]
+macro_rules! irq_entry {
+    () => {
+        r#"
+irq_handler_asm:
+    sub lr, lr, #4               @ Correct LR for IRQ return
+    srsdb sp!, #{mode_irq}       @ Save LR_irq and SPSR_irq
+    push {{r0-r3, r12}}          @ Push caller-saved registers
+    and r1, sp, #4               @ Align the stack to 8 bytes for AAPCS
+    sub sp, sp, r1
+    push {{r1, lr}}              @ Remember the adjustment
+"#
+    };
+}
+
+/// Nested IRQ entry: the handler runs in SVC mode so a preempting IRQ
+/// cannot clobber LR_irq/SPSR_irq; the dispatcher re-enables IRQs
+#[cfg(feature = "nested-irq")]
+macro_rules! irq_entry {
+    () => {
+        r#"
+irq_handler_asm:
+    sub lr, lr, #4               @ Correct LR for IRQ return
+    srsdb sp!, #{mode_svc}       @ Save LR_irq and SPSR_irq on the SVC stack
+    cps #{mode_svc}              @ Switch to SVC mode (IRQs stay masked)
+    push {{r0-r3, r12}}          @ Push caller-saved registers
+    and r1, sp, #4               @ Align the stack to 8 bytes for AAPCS
+    sub sp, sp, r1
+    push {{r1, lr}}              @ Remember the adjustment (lr is the interrupted LR_svc)
+"#
+    };
+}
+
+global_asm!(
+    r#"
+    .syntax unified
+    .arm
+
+.section .text._vector_table, "ax", %progbits
+.align 5
+.global _vector_table
+
+_vector_table:
+    b _reset                /* 0x00: Reset */
+    b undef_handler_asm     /* 0x04: Undefined Instruction */
+    b swi_handler_asm       /* 0x08: Software Interrupt (SVC) */
+    b prefetch_abort_asm    /* 0x0C: Prefetch Abort */
+    b data_abort_asm        /* 0x10: Data Abort */
+    b .                     /* 0x14: Reserved */
+    b irq_handler_asm       /* 0x18: IRQ */
+    b fiq_handler_asm       /* 0x1C: FIQ */
+
+.section .boot, "ax", %progbits
+.align 4
+.global _reset
+
+_reset:
+    cpsid if                     @ Disable IRQ and FIQ
+
+    /* 1. Initialize EL2 (Hypervisor) */
+    /* Set the Hyp and EL1 Vector Base Addresses */
+    ldr r0, =_hyp_vector_table
+    mcr p15, 4, r0, c12, c0, 0   @ HVBAR
+    ldr r0, =_vector_table
+    mcr p15, 0, r0, c12, c0, 0   @ VBAR
+    dsb sy
+    isb
+
+    /* 2. Hyp mode stack, used by Hyp exception entry and el2_init */
+    ldr sp, =__stack_hyp_top
+"#,
+    fpu_init!(),
+    tcm_init!(),
+    r#"
+
+    /* 3. Copy .data, .ramfunc and the TCM sections from their load addresses */
+    ldr r0, =_sdata
+    ldr r1, =_edata
+    ldr r2, =_sidata
+    bl copy_section
+    ldr r0, =_sramfunc
+    ldr r1, =_eramfunc
+    ldr r2, =_siramfunc
+    bl copy_section
+"#,
+    tcm_copy!(),
+    r#"
+    /* 4. Clear BSS (Recommended to do in ASM for Rust) */
+    ldr r0, =__bss_start
+    ldr r1, =__bss_end
+    mov r2, #0
+
+1:
+    cmp r0, r1
+    bge 2f
+    str r2, [r0], #4
+    b 1b
+2:
+    dsb sy
+    isb
+
+    /* 5. EL2 setup hook (EL2 MPU, HCR, ...); statics are initialized */
+    bl el2_init
+
+    /* Enable Timer access from EL1 */
+    mov r0, #0x1                  @ Enable EL1 access to physical timer
+    mcr p15, 4, r0, c14, c2, 0
+    isb
+
+    mrs r0, cpsr
+    bic r0, r0, #0x1F             @ Clear mode bits
+    orr r0, r0, #{mode_svc}       @ Return to SVC mode
+    msr spsr_cxsf, r0
+
+    /* Set ELR_hyp to our EL1 entry point */
+    ldr r0, =el1_entry
+    msr elr_hyp, r0
+
+    /* 6. Transition to EL1 */
+    eret
+
+el1_entry:
+    /* Now in EL1 (SVC mode) */
+
+    /* 7. Setup Stacks */
+    /* IRQ Mode */
+    mrs r0, cpsr
+    bic r0, r0, #0x1F             @ Clear mode bits
+    orr r0, r0, #({mode_irq} | {i_bit} | {f_bit})  @ IRQ Mode with IRQ/FIQ disabled
+    msr cpsr_c, r0
+    ldr sp, =__stack_irq_top
+
+    /* FIQ Mode */
+    mrs r0, cpsr
+    bic r0, r0, #0x1F             @ Clear mode bits
+    orr r0, r0, #({mode_fiq} | {i_bit} | {f_bit})  @ FIQ Mode with IRQ/FIQ disabled
+    msr cpsr_c, r0
+    ldr sp, =__stack_fiq_top
+
+    /* Abort Mode */
+    mrs r0, cpsr
+    bic r0, r0, #0x1F             @ Clear mode bits
+    orr r0, r0, #({mode_abt} | {i_bit} | {f_bit})  @ Abort Mode with IRQ/FIQ disabled
+    msr cpsr_c, r0
+    ldr sp, =__stack_abt_top
+
+    /* Undefined Mode */
+    mrs r0, cpsr
+    bic r0, r0, #0x1F             @ Clear mode bits
+    orr r0, r0, #({mode_und} | {i_bit} | {f_bit})  @ Undefined Mode with IRQ/FIQ disabled
+    msr cpsr_c, r0
+    ldr sp, =__stack_und_top
+
+    /* SVC Mode */
+    mrs r0, cpsr
+    bic r0, r0, #0x1F             @ Clear mode bits
+    orr r0, r0, #({mode_svc} | {i_bit} | {f_bit})  @ SVC Mode with IRQ/FIQ disabled
+    msr cpsr_c, r0
+    ldr sp, =__stack_svc_top
+
+    /* Jump to Rust */
+    bl rust_main
+
+halt_loop:
+    b halt_loop
+
+/* Default EL2 setup hook: nothing to do. Override by defining */
+/* `extern "C" fn el2_init()` with #[unsafe(no_mangle)]. */
+.weak el2_init
+.type el2_init, %function
+el2_init:
+    bx lr
+
+/* Copy words from r2 to r0 until r0 reaches r1; nothing to do if the */
+/* section is already at its run address. Clobbers r0-r3. */
+copy_section:
+    cmp r0, r2
+    bxeq lr
+3:
+    cmp r0, r1
+    bxhs lr
+    ldr r3, [r2], #4
+    str r3, [r0], #4
+    b 3b
+
+/* Store the interrupted mode's banked SP and LR in the ExceptionFrame at r4. */
+/* User mode registers are read through System mode. For an exception taken */
+/* from the handler's own mode, SP is the value before entry and LR (already */
+/* overwritten by the exception) is stored as 0. Clobbers r0-r2. */
+save_banked_sp_lr:
+    mrs r0, spsr
+    and r0, r0, #0x1F             @ Interrupted mode
+    cmp r0, #{mode_usr}
+    moveq r0, #{mode_sys}
+    mrs r1, cpsr
+    and r2, r1, #0x1F             @ Exception mode
+    cmp r0, r2
+    beq 4f
+    bic r2, r1, #0x1F
+    orr r2, r2, r0
+    msr cpsr_c, r2                @ Switch to the interrupted mode, IRQ/FIQ stay masked
+    mov r0, sp
+    mov r2, lr
+    msr cpsr_c, r1                @ Back to the exception mode
+    stm r4, {{r0, r2}}
+    bx lr
+4:
+    add r0, r4, #{frame_size}     @ SP before the exception pushed the frame
+    mov r2, #0
+    stm r4, {{r0, r2}}
+    bx lr
+
+/* --- Exception Handlers --- */
+
+/* Fault and SVC entry: build an ExceptionFrame (interrupted SP and LR, r0-r12, */
+/* return address, SPSR) on the exception mode's stack and pass its address to */
+/* Rust in r0. The frame address is kept in r4 (callee-saved) while the stack */
+/* below it is aligned to 8 bytes. The handler may edit the frame; r0-r12, the */
+/* return address and SPSR are restored on return. */
+
+.align 4
+undef_handler_asm:
+    srsdb sp!, #{mode_und}       @ Push LR_und and SPSR_und
+    push {{r0-r12}}              @ Push all general registers
+    sub sp, sp, #8               @ Room for the interrupted mode's SP and LR
+    mov r4, sp                   @ ExceptionFrame address
+    bl save_banked_sp_lr
+    ldr r0, [r4, #{frame_lr}]    @ LR_und is the undefined instruction + 4 in
+    mrs r1, spsr                 @ ARM state and + 2 in Thumb state, whatever
+    tst r1, #{t_bit}             @ the Thumb instruction's size
+    subeq r0, r0, #4
+    subne r0, r0, #2
+    str r0, [r4, #{frame_lr}]    @ Report (and retry) the instruction itself
+    bic sp, sp, #7               @ Align the stack to 8 bytes for AAPCS
+"#,
+    fpu_save!(),
+    r#"
+    mov r0, r4                   @ Pass the ExceptionFrame to Rust
+    bl rust_undef_handler
+"#,
+    fpu_restore!(),
+    r#"
+    add sp, r4, #8               @ Drop the banked SP/LR (not restored)
+    pop {{r0-r12}}
+    rfeia sp!                    @ Return via the saved LR and SPSR
+
+data_abort_asm:
+    sub lr, lr, #8               @ LR points at the faulting instruction
+    srsdb sp!, #{mode_abt}       @ Push return address and SPSR_abt
+    push {{r0-r12}}              @ Push all general registers
+    sub sp, sp, #8               @ Room for the interrupted mode's SP and LR
+    mov r4, sp                   @ ExceptionFrame address
+    bl save_banked_sp_lr
+    bic sp, sp, #7               @ Align the stack to 8 bytes for AAPCS
+"#,
+    fpu_save!(),
+    r#"
+    mov r0, r4                   @ Pass the ExceptionFrame to Rust
+    bl rust_data_abort_handler
+"#,
+    fpu_restore!(),
+    r#"
+    add sp, r4, #8               @ Drop the banked SP/LR (not restored)
+    pop {{r0-r12}}
+    rfeia sp!                    @ Return via the saved LR and SPSR
+
+prefetch_abort_asm:
+    sub lr, lr, #4               @ LR points at the faulting instruction
+    srsdb sp!, #{mode_abt}       @ Push return address and SPSR_abt
+    push {{r0-r12}}              @ Push all general registers
+    sub sp, sp, #8               @ Room for the interrupted mode's SP and LR
+    mov r4, sp                   @ ExceptionFrame address
+    bl save_banked_sp_lr
+    bic sp, sp, #7               @ Align the stack to 8 bytes for AAPCS
+"#,
+    fpu_save!(),
+    r#"
+    mov r0, r4                   @ Pass the ExceptionFrame to Rust
+    bl rust_prefetch_abort_handler
+"#,
+    fpu_restore!(),
+    r#"
+    add sp, r4, #8               @ Drop the banked SP/LR (not restored)
+    pop {{r0-r12}}
+    rfeia sp!                    @ Return via the saved LR and SPSR
+
+swi_handler_asm:
+    srsdb sp!, #{mode_svc}       @ Push LR_svc (after the SVC) and SPSR_svc
+    push {{r0-r12}}              @ Push all general registers
+    sub sp, sp, #8               @ Room for the interrupted mode's SP and LR
+    mov r4, sp                   @ ExceptionFrame address
+    bl save_banked_sp_lr
+    bic sp, sp, #7               @ Align the stack to 8 bytes for AAPCS
+"#,
+    fpu_save!(),
+    r#"
+    mov r0, r4                   @ Pass the ExceptionFrame to Rust
+    bl rust_swi_handler          @ Result is written to the frame's r0
+"#,
+    fpu_restore!(),
+    r#"
+    add sp, r4, #8               @ Drop the banked SP/LR (not restored)
+    pop {{r0-r12}}
+    rfeia sp!                    @ Return via the saved LR and SPSR
+"#,
+    irq_entry!(),
+    fpu_save!(),
+    r#"
+    bl rust_irq_handler          @ Your Rust IRQ dispatcher
+"#,
+    fpu_restore!(),
+    r#"
+    pop {{r1, lr}}
+    add sp, sp, r1               @ Undo the alignment
+    pop {{r0-r3, r12}}           @ Restore
+    rfeia sp!                    @ Return to interrupted code
+
+fiq_handler_asm:
+    sub lr, lr, #4               @ Correct LR for FIQ return
+    srsdb sp!, #{mode_fiq}       @ Save LR_fiq and SPSR_fiq
+    push {{r0-r3, r12}}          @ Push context (r8-r12 are banked, r12 saved for Rust)
+    and r1, sp, #4               @ Align the stack to 8 bytes for AAPCS
+    sub sp, sp, r1
+    push {{r1, lr}}              @ Remember the adjustment
+"#,
+    fpu_save!(),
+    r#"
+    bl rust_fiq_handler          @ Your Rust FIQ dispatcher
+"#,
+    fpu_restore!(),
+    r#"
+    pop {{r1, lr}}
+    add sp, sp, r1               @ Undo the alignment
+    pop {{r0-r3, r12}}           @ Restore
+    rfeia sp!                    @ Return to interrupted code
+
+halt:
+    wfe
+    b halt
+"#,
+    mode_usr = const ARM_MODE_USR,
+    mode_sys = const ARM_MODE_SYS,
+    mode_fiq = const ARM_MODE_FIQ,
+    mode_irq = const ARM_MODE_IRQ,
+    mode_svc = const ARM_MODE_SVC,
+    mode_abt = const ARM_MODE_ABT,
+    mode_und = const ARM_MODE_UND,
+    i_bit = const CPSR_I_BIT,
+    f_bit = const CPSR_F_BIT,
+    frame_size = const size_of::<ExceptionFrame>(),
+    frame_lr = const offset_of!(ExceptionFrame, lr),
+    t_bit = const SPSR_T_BIT,
+);
@@ -0,0 +1,97 @@
+/// `critical-section` implementations
+///
+/// With "critical-section-single-core", a critical section masks IRQs on the
+/// calling core and restores the previous CPSR.I state on release, so nested
+/// sections leave interrupts masked until the outermost one ends.
+///
+/// With "critical-section-multi-core", the calling core additionally takes a
+/// global spinlock. The lock records its owner, so a core re-entering a
+/// critical section it already holds does not deadlock.
+///
+/// FIQs are not masked: FIQ handlers must not enter critical sections.
+#[cfg(feature = "critical-section-multi-core")]
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::irq::{enable_irq, save_and_disable_irq};
+#[cfg(feature = "critical-section-multi-core")]
+use crate::system::read_mpidr;
+
+#[cfg(all(
+    feature = "critical-section-single-core",
+    feature = "critical-section-multi-core"
+))]
+compile_error!(
+    "features \"critical-section-single-core\" and \"critical-section-multi-core\" are mutually exclusive"
+);
+
+#[cfg(feature = "critical-section-single-core")]
+struct SingleCoreCriticalSection;
+
+#[cfg(feature = "critical-section-single-core")]
+critical_section::set_impl!(SingleCoreCriticalSection);
+
+#[cfg(feature = "critical-section-single-core")]
+unsafe impl critical_section::Impl for SingleCoreCriticalSection {
+    unsafe fn acquire() -> bool {
+        save_and_disable_irq().irq_enabled()
+    }
+
+    unsafe fn release(irq_was_enabled: bool) {
+        if irq_was_enabled {
+            enable_irq();
+        }
+    }
+}
+
+/// Restore state bit: IRQs were enabled before the section
+#[cfg(feature = "critical-section-multi-core")]
+const STATE_IRQ_ENABLED: u32 = 1 << 0;
+
+/// Restore state bit: this (outermost) section took the lock
+#[cfg(feature = "critical-section-multi-core")]
+const STATE_LOCK_TAKEN: u32 = 1 << 1;
+
+/// Owner of the global lock: MPIDR affinity of the holding core plus one, or 0 if free
+///
+/// Compare-exchange on an `AtomicU32` compiles to an LDREX/STREX loop.
+#[cfg(feature = "critical-section-multi-core")]
+static LOCK_OWNER: AtomicU32 = AtomicU32::new(0);
+
+#[cfg(feature = "critical-section-multi-core")]
+struct MultiCoreCriticalSection;
+
+#[cfg(feature = "critical-section-multi-core")]
+critical_section::set_impl!(MultiCoreCriticalSection);
+
+#[cfg(feature = "critical-section-multi-core")]
+unsafe impl critical_section::Impl for MultiCoreCriticalSection {
+    unsafe fn acquire() -> u32 {
+        // Mask IRQs first so the lock is never held across a local interrupt
+        let mut state = if save_and_disable_irq().irq_enabled() {
+            STATE_IRQ_ENABLED
+        } else {
+            0
+        };
+
+        let me = (read_mpidr() & 0x00FF_FFFF) + 1;
+        if LOCK_OWNER.load(Ordering::Relaxed) != me {
+            while LOCK_OWNER
+                .compare_exchange_weak(0, me, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+            {
+                core::hint::spin_loop();
+            }
+            state |= STATE_LOCK_TAKEN;
+        }
+        state
+    }
+
+    unsafe fn release(state: u32) {
+        if state & STATE_LOCK_TAKEN != 0 {
+            LOCK_OWNER.store(0, Ordering::Release);
+        }
+        if state & STATE_IRQ_ENABLED != 0 {
+            enable_irq();
+        }
+    }
+}
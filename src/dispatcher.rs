@@ -0,0 +1,291 @@
+/// Interrupt dispatcher
+///
+/// Maps interrupt IDs to Rust handlers and performs the acknowledge / dispatch /
+/// end-of-interrupt sequence on behalf of the application. With the
+/// "irq-dispatcher" feature enabled, the runtime provides `rust_irq_handler`
+/// and `rust_fiq_handler` and routes every IRQ through [`dispatch_irq`] and
+/// every FIQ through [`dispatch_fiq`]. The "nested-irq" feature additionally lets
+/// higher-priority IRQs preempt a running IRQ handler.
+///
+/// When the CPU interface runs with `EoiMode::DropOnly`, handlers registered
+/// with [`register_deferred_irq_handler`] only get their priority dropped on
+/// return; the interrupt stays active (and cannot re-trigger) until thread
+/// code on the same core calls [`complete_deferred_irq`].
+use core::cell::UnsafeCell;
+use core::ptr::{read_volatile, write_volatile};
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::gic::{
+    Affinity, EoiMode, Gic, IntId, MAX_CORES_PER_CLUSTER, SPECIAL_INTERRUPT_ID_START,
+};
+#[cfg(feature = "nested-irq")]
+use crate::irq::{disable_irq, enable_irq};
+
+/// Interrupt handler, called with the acknowledged interrupt ID
+pub type InterruptHandler = fn(intid: IntId);
+
+/// Spurious interrupt hook, called with the special interrupt ID (1020-1023)
+pub type SpuriousHandler = fn(intid: u32);
+
+/// Number of handler slots (INTIDs 0-1019)
+const MAX_HANDLERS: usize = SPECIAL_INTERRUPT_ID_START as usize;
+
+/// Handler table shared between thread context and the interrupt handler
+///
+/// Entries are single words written and read with volatile accesses, so a
+/// handler is always observed either fully registered or not at all.
+pub(crate) struct HandlerTable<T: Copy, const N: usize>(UnsafeCell<[Option<T>; N]>);
+
+unsafe impl<T: Copy, const N: usize> Sync for HandlerTable<T, N> {}
+
+impl<T: Copy, const N: usize> HandlerTable<T, N> {
+    pub(crate) const fn new() -> Self {
+        Self(UnsafeCell::new([None; N]))
+    }
+
+    pub(crate) fn get(&self, index: usize) -> Option<T> {
+        if index >= N {
+            return None;
+        }
+        unsafe { read_volatile((self.0.get() as *const Option<T>).add(index)) }
+    }
+
+    pub(crate) fn set(&self, index: usize, handler: Option<T>) -> bool {
+        if index >= N {
+            return false;
+        }
+        unsafe { write_volatile((self.0.get() as *mut Option<T>).add(index), handler) };
+        true
+    }
+}
+
+/// One bit per interrupt ID (0-1023), safe to update from any context
+struct InterruptBitmap([AtomicU32; 32]);
+
+impl InterruptBitmap {
+    const fn new() -> Self {
+        Self([const { AtomicU32::new(0) }; 32])
+    }
+
+    fn set(&self, intid: u32, value: bool) {
+        let word = &self.0[(intid / 32) as usize];
+        let mask = 1u32 << (intid % 32);
+        if value {
+            word.fetch_or(mask, Ordering::AcqRel);
+        } else {
+            word.fetch_and(!mask, Ordering::AcqRel);
+        }
+    }
+
+    fn get(&self, intid: u32) -> bool {
+        let word = &self.0[(intid / 32) as usize];
+        (word.load(Ordering::Acquire) & (1u32 << (intid % 32))) != 0
+    }
+
+    /// Clear the bit and report whether it was set
+    fn take(&self, intid: u32) -> bool {
+        let word = &self.0[(intid / 32) as usize];
+        let mask = 1u32 << (intid % 32);
+        (word.fetch_and(!mask, Ordering::AcqRel) & mask) != 0
+    }
+}
+
+static IRQ_HANDLERS: HandlerTable<InterruptHandler, MAX_HANDLERS> = HandlerTable::new();
+
+static FIQ_HANDLERS: HandlerTable<InterruptHandler, MAX_HANDLERS> = HandlerTable::new();
+
+static SPURIOUS_HANDLER: HandlerTable<SpuriousHandler, 1> = HandlerTable::new();
+
+/// Interrupts whose deactivation is left to thread context in `EoiMode::DropOnly`
+///
+/// Shared by all cores, like the handler tables: it records how an INTID is
+/// handled, not which core has it active.
+static DEFERRED_DEACTIVATION: InterruptBitmap = InterruptBitmap::new();
+
+/// Interrupts that have had their priority dropped but are still active, one
+/// set per core (indexed by MPIDR.Aff0): SGIs and PPIs are banked per core,
+/// and only the core that acknowledged an interrupt can deactivate it
+static AWAITING_DEACTIVATION: [InterruptBitmap; MAX_CORES_PER_CLUSTER] =
+    [const { InterruptBitmap::new() }; MAX_CORES_PER_CLUSTER];
+
+/// The calling core's set of interrupts awaiting deactivation
+fn awaiting_deactivation() -> Option<&'static InterruptBitmap> {
+    AWAITING_DEACTIVATION.get(Affinity::current().aff0 as usize)
+}
+
+/// Register a handler for an interrupt ID
+///
+/// Replaces any previously registered handler.
+///
+/// # Arguments
+/// * `intid` - Interrupt ID
+/// * `handler` - Function called when the interrupt is acknowledged
+pub fn register_irq_handler(intid: impl Into<IntId>, handler: InterruptHandler) {
+    let intid = intid.into();
+    IRQ_HANDLERS.set(intid.value() as usize, Some(handler));
+    DEFERRED_DEACTIVATION.set(intid.value(), false);
+}
+
+/// Register a handler whose interrupt is deactivated later from thread context
+///
+/// In `EoiMode::DropOnly` the dispatcher drops the running priority when the
+/// handler returns but leaves the interrupt active, so a level-triggered line
+/// does not re-trigger until [`complete_deferred_irq`] is called. In
+/// `EoiMode::DropAndDeactivate` this behaves like [`register_irq_handler`].
+///
+/// # Arguments
+/// * `intid` - Interrupt ID
+/// * `handler` - Function called when the interrupt is acknowledged
+pub fn register_deferred_irq_handler(intid: impl Into<IntId>, handler: InterruptHandler) {
+    let intid = intid.into();
+    IRQ_HANDLERS.set(intid.value() as usize, Some(handler));
+    DEFERRED_DEACTIVATION.set(intid.value(), true);
+}
+
+/// Remove the handler for an interrupt ID
+///
+/// # Arguments
+/// * `intid` - Interrupt ID
+pub fn unregister_irq_handler(intid: impl Into<IntId>) {
+    let intid = intid.into();
+    IRQ_HANDLERS.set(intid.value() as usize, None);
+    DEFERRED_DEACTIVATION.set(intid.value(), false);
+}
+
+/// Deactivate an interrupt left active by a deferred handler
+///
+/// Writes ICC_DIR only if the interrupt's priority was dropped by the
+/// dispatcher on the calling core and it has not been deactivated since, so
+/// stray or repeated calls, and calls from another core, are harmless.
+///
+/// # Arguments
+/// * `intid` - Interrupt ID
+///
+/// # Returns
+/// `true` if the interrupt was awaiting deactivation on this core and has now
+/// been deactivated
+pub fn complete_deferred_irq(intid: impl Into<IntId>) -> bool {
+    let intid = intid.into();
+    let Some(awaiting) = awaiting_deactivation() else {
+        return false;
+    };
+    if !awaiting.take(intid.value()) {
+        return false;
+    }
+    Gic::write_deactivate_interrupt(intid.value());
+    true
+}
+
+/// Check whether an interrupt taken by this core has been dropped but not yet
+/// deactivated
+///
+/// # Arguments
+/// * `intid` - Interrupt ID
+pub fn is_awaiting_deactivation(intid: impl Into<IntId>) -> bool {
+    let intid = intid.into();
+    awaiting_deactivation().is_some_and(|awaiting| awaiting.get(intid.value()))
+}
+
+/// Register a FIQ handler for a Group 0 interrupt ID
+///
+/// The interrupt must also be placed in Group 0 with `Gic::set_interrupt_group`
+/// for it to be signalled as FIQ.
+///
+/// # Arguments
+/// * `intid` - Interrupt ID
+/// * `handler` - Function called when the interrupt is acknowledged
+pub fn register_fiq_handler(intid: impl Into<IntId>, handler: InterruptHandler) {
+    FIQ_HANDLERS.set(intid.into().value() as usize, Some(handler));
+}
+
+/// Remove the FIQ handler for an interrupt ID
+///
+/// # Arguments
+/// * `intid` - Interrupt ID
+pub fn unregister_fiq_handler(intid: impl Into<IntId>) {
+    FIQ_HANDLERS.set(intid.into().value() as usize, None);
+}
+
+/// Register the hook called when a special interrupt ID (1020-1023) is read
+///
+/// Special IDs are never passed to the regular handlers and are not
+/// signalled with an end-of-interrupt.
+pub fn register_spurious_handler(handler: SpuriousHandler) {
+    SPURIOUS_HANDLER.set(0, Some(handler));
+}
+
+/// Acknowledge, dispatch and complete one Group 1 interrupt
+///
+/// Reads ICC_IAR1, calls the registered handler (if any) and writes ICC_EOIR1.
+/// In `EoiMode::DropOnly` it also writes ICC_DIR, unless the handler was
+/// registered with [`register_deferred_irq_handler`].
+/// Applications that provide their own `rust_irq_handler` can call this to
+/// reuse the dispatcher.
+pub fn dispatch_irq() {
+    let raw = Gic::get_interrupt_id(Gic::read_interrupt_ack());
+    let Ok(intid) = IntId::new(raw) else {
+        if let Some(handler) = SPURIOUS_HANDLER.get(0) {
+            handler(raw);
+        }
+        return;
+    };
+
+    if let Some(handler) = IRQ_HANDLERS.get(raw as usize) {
+        // With "nested-irq", the entry stub has moved to SVC mode, so IRQs can be
+        // unmasked once this one is acknowledged. The GIC then only signals
+        // interrupts whose group priority (see ICC_BPR1) beats the running priority.
+        #[cfg(feature = "nested-irq")]
+        enable_irq();
+
+        handler(intid);
+
+        #[cfg(feature = "nested-irq")]
+        disable_irq();
+    }
+
+    Gic::write_end_of_interrupt_group1(raw);
+
+    if Gic::get_eoi_mode() == EoiMode::DropOnly {
+        // A core outside the tracked range cannot defer, so it deactivates now
+        match awaiting_deactivation() {
+            Some(awaiting) if DEFERRED_DEACTIVATION.get(raw) => awaiting.set(raw, true),
+            _ => Gic::write_deactivate_interrupt(raw),
+        }
+    }
+}
+
+/// Acknowledge, dispatch and complete one Group 0 interrupt
+///
+/// Reads ICC_IAR0, calls the registered FIQ handler (if any) and writes ICC_EOIR0.
+pub fn dispatch_fiq() {
+    let raw = Gic::get_interrupt_id(Gic::read_interrupt_ack_group0());
+    let Ok(intid) = IntId::new(raw) else {
+        if let Some(handler) = SPURIOUS_HANDLER.get(0) {
+            handler(raw);
+        }
+        return;
+    };
+
+    if let Some(handler) = FIQ_HANDLERS.get(raw as usize) {
+        handler(intid);
+    }
+
+    Gic::write_end_of_interrupt_group0(raw);
+
+    // FIQs are never deferred; complete them straight away in DropOnly mode
+    if Gic::get_eoi_mode() == EoiMode::DropOnly {
+        Gic::write_deactivate_interrupt(raw);
+    }
+}
+
+#[cfg(feature = "irq-dispatcher")]
+#[unsafe(no_mangle)]
+pub extern "C" fn rust_irq_handler() {
+    dispatch_irq();
+}
+
+#[cfg(feature = "irq-dispatcher")]
+#[unsafe(no_mangle)]
+pub extern "C" fn rust_fiq_handler() {
+    dispatch_fiq();
+}
@@ -0,0 +1,303 @@
+/// Exception frames and fault decoding
+///
+/// The undefined instruction and abort entry stubs save every general register
+/// (r0-r12 and the interrupted mode's banked SP and LR) plus the return
+/// address and SPSR in an [`ExceptionFrame`] and pass it to the Rust handler.
+/// r0-r12, the return address and SPSR are restored when the handler returns,
+/// so a handler can recover from a fault by editing them (for example
+/// advancing `lr` past the faulting instruction).
+use core::fmt;
+
+use crate::irq::ProcessorMode;
+use crate::system::{read_dfar, read_dfsr, read_ifar, read_ifsr};
+
+/// SPSR T bit: the exception was taken from Thumb state
+pub(crate) const SPSR_T_BIT: u32 = 1 << 5;
+
+/// Fault status register STATUS field [5:0]
+const FSR_STATUS_MASK: u32 = 0x3F;
+
+/// DFSR WnR bit: the access was a write
+const DFSR_WNR: u32 = 1 << 11;
+
+/// HSR exception class field [31:26]
+const HSR_EC_SHIFT: u32 = 26;
+
+/// HSR instruction specific syndrome field [24:0]
+const HSR_ISS_MASK: u32 = 0x01FF_FFFF;
+
+/// Register state saved on exception entry
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ExceptionFrame {
+    /// SP of the interrupted mode (User and System share one); not restored
+    pub sp: u32,
+    /// LR of the interrupted mode, usually the interrupted function's return
+    /// address; not restored. 0 if the exception was taken from its own mode,
+    /// which overwrites it
+    pub lr_mode: u32,
+    /// r0-r12 at the time of the exception
+    pub r: [u32; 13],
+    /// Return address: the faulting instruction for aborts and undefined
+    /// instructions, so returning retries it; the instruction after the SVC
+    /// for supervisor calls
+    pub lr: u32,
+    /// Saved program status register of the interrupted code
+    pub spsr: u32,
+}
+
+impl ExceptionFrame {
+    /// Whether the interrupted code was executing Thumb instructions
+    pub fn is_thumb(&self) -> bool {
+        (self.spsr & SPSR_T_BIT) != 0
+    }
+
+    /// Size in bytes of the instruction at `lr`: 4 in ARM state, 2 or 4 in
+    /// Thumb state depending on its first halfword
+    ///
+    /// A handler that emulates the faulting instruction adds this to `lr` to
+    /// resume after it.
+    pub fn instruction_len(&self) -> u32 {
+        if !self.is_thumb() {
+            return 4;
+        }
+        let halfword = unsafe { core::ptr::read_volatile(self.lr as *const u16) };
+        // 0b11101, 0b11110 and 0b11111 in bits [15:11] start a 32-bit instruction
+        if halfword >> 11 >= 0b11101 { 4 } else { 2 }
+    }
+
+    /// Mode of the interrupted code
+    pub fn mode(&self) -> ProcessorMode {
+        ProcessorMode::from_bits(self.spsr)
+    }
+}
+
+impl fmt::Display for ExceptionFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, value) in self.r.iter().enumerate() {
+            let sep = if i % 4 == 3 { "\n" } else { "  " };
+            write!(f, "r{:<2}={:#010x}{}", i, value, sep)?;
+        }
+        writeln!(
+            f,
+            "\nsp ={:#010x}  lr_mode={:#010x}\nlr ={:#010x}  spsr={:#010x} ({:?}{})",
+            self.sp,
+            self.lr_mode,
+            self.lr,
+            self.spsr,
+            self.mode(),
+            if self.is_thumb() { ", Thumb" } else { "" }
+        )
+    }
+}
+
+/// Fault cause, decoded from the DFSR/IFSR STATUS field
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FaultKind {
+    /// No MPU region matched and the background region did not apply
+    Background { level: u8 },
+    /// MPU region permissions forbid the access
+    Permission { level: u8 },
+    /// Unaligned access to Device memory or with alignment checking enabled
+    Alignment,
+    /// Synchronous external abort from the bus
+    SyncExternal,
+    /// Asynchronous (SError) external abort from the bus
+    AsyncExternal,
+    /// Synchronous parity or ECC error
+    SyncEcc,
+    /// Asynchronous parity or ECC error
+    AsyncEcc,
+    /// Debug event (breakpoint or watchpoint)
+    Debug,
+    /// Encoding not listed above
+    Unknown(u8),
+}
+
+impl FaultKind {
+    /// Decode a DFSR or IFSR value
+    pub const fn from_fsr(fsr: u32) -> Self {
+        let status = (fsr & FSR_STATUS_MASK) as u8;
+        match status {
+            0b00_0100..=0b00_0111 => FaultKind::Background {
+                level: status & 0x3,
+            },
+            0b00_1100..=0b00_1111 => FaultKind::Permission {
+                level: status & 0x3,
+            },
+            0b01_0000 => FaultKind::SyncExternal,
+            0b01_0001 => FaultKind::AsyncExternal,
+            0b01_1000 => FaultKind::SyncEcc,
+            0b01_1001 => FaultKind::AsyncEcc,
+            0b10_0001 => FaultKind::Alignment,
+            0b10_0010 => FaultKind::Debug,
+            other => FaultKind::Unknown(other),
+        }
+    }
+}
+
+/// A decoded data or prefetch abort
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fault {
+    /// Cause of the fault
+    pub kind: FaultKind,
+    /// Faulting address (DFAR/IFAR); not valid for asynchronous aborts
+    pub address: u32,
+    /// Raw fault status register value (DFSR/IFSR)
+    pub status: u32,
+    /// The faulting access was a write (always `false` for prefetch aborts)
+    pub is_write: bool,
+}
+
+impl Fault {
+    /// Read and decode DFSR/DFAR; call from the data abort handler
+    pub fn data_abort() -> Self {
+        let status = read_dfsr();
+        Self {
+            kind: FaultKind::from_fsr(status),
+            address: read_dfar(),
+            status,
+            is_write: (status & DFSR_WNR) != 0,
+        }
+    }
+
+    /// Read and decode IFSR/IFAR; call from the prefetch abort handler
+    pub fn prefetch_abort() -> Self {
+        let status = read_ifsr();
+        Self {
+            kind: FaultKind::from_fsr(status),
+            address: read_ifar(),
+            status,
+            is_write: false,
+        }
+    }
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} fault at {:#010x}{} (FSR={:#010x})",
+            self.kind,
+            self.address,
+            if self.is_write { " (write)" } else { "" },
+            self.status
+        )
+    }
+}
+
+/// Entry of the Hyp vector table an exception was taken through
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HypVector {
+    /// Undefined instruction in Hyp mode
+    Undefined,
+    /// HVC instruction
+    HypervisorCall,
+    /// Prefetch abort in Hyp mode
+    PrefetchAbort,
+    /// Data abort in Hyp mode
+    DataAbort,
+    /// Exception from EL1/EL0 routed to Hyp mode, including EL2 MPU faults
+    /// while EL1 runs inside the HypMpu window
+    HypTrap,
+    Irq,
+    Fiq,
+}
+
+impl HypVector {
+    /// Decode a Hyp vector table offset
+    pub const fn from_offset(offset: u32) -> Option<Self> {
+        match offset {
+            0x04 => Some(HypVector::Undefined),
+            0x08 => Some(HypVector::HypervisorCall),
+            0x0C => Some(HypVector::PrefetchAbort),
+            0x10 => Some(HypVector::DataAbort),
+            0x14 => Some(HypVector::HypTrap),
+            0x18 => Some(HypVector::Irq),
+            0x1C => Some(HypVector::Fiq),
+            _ => None,
+        }
+    }
+}
+
+/// Register and syndrome state saved on entry to Hyp mode
+///
+/// Passed to `rust_hyp_trap_handler`. r0-r12, `lr`, the return address and
+/// SPSR are restored when the handler returns, and the exception returns
+/// with `eret`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct HypTrapFrame {
+    /// Offset of the Hyp vector table entry taken, see [`HypTrapFrame::vector`]
+    pub vector_offset: u32,
+    /// Hyp Syndrome Register
+    pub hsr: u32,
+    /// Hyp Data Fault Address Register
+    pub hdfar: u32,
+    /// Hyp Instruction Fault Address Register
+    pub hifar: u32,
+    /// Hyp IPA Fault Address Register
+    pub hpfar: u32,
+    /// Return address (ELR_hyp): the faulting instruction for aborts and traps
+    pub elr: u32,
+    /// Saved program status register of the interrupted code (SPSR_hyp)
+    pub spsr: u32,
+    /// r0-r12 at the time of the exception
+    pub r: [u32; 13],
+    /// LR_usr, which Hyp mode shares with User and System mode
+    pub lr: u32,
+}
+
+impl HypTrapFrame {
+    /// Vector table entry the exception was taken through
+    pub const fn vector(&self) -> Option<HypVector> {
+        HypVector::from_offset(self.vector_offset)
+    }
+
+    /// HSR.EC: class of the exception (e.g. 0x24 for a data abort from EL1/EL0)
+    pub const fn exception_class(&self) -> u8 {
+        (self.hsr >> HSR_EC_SHIFT) as u8
+    }
+
+    /// HSR.ISS: class-specific syndrome
+    pub const fn iss(&self) -> u32 {
+        self.hsr & HSR_ISS_MASK
+    }
+
+    /// Fault cause from the ISS status field; only meaningful for aborts
+    pub const fn fault_kind(&self) -> FaultKind {
+        FaultKind::from_fsr(self.hsr)
+    }
+
+    /// Mode of the interrupted code
+    pub fn mode(&self) -> ProcessorMode {
+        ProcessorMode::from_bits(self.spsr)
+    }
+}
+
+impl fmt::Display for HypTrapFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:?}  HSR={:#010x} (EC={:#04x})  HDFAR={:#010x}  HIFAR={:#010x}  HPFAR={:#010x}",
+            self.vector(),
+            self.hsr,
+            self.exception_class(),
+            self.hdfar,
+            self.hifar,
+            self.hpfar
+        )?;
+        for (i, value) in self.r.iter().enumerate() {
+            let sep = if i % 4 == 3 { "\n" } else { "  " };
+            write!(f, "r{:<2}={:#010x}{}", i, value, sep)?;
+        }
+        writeln!(
+            f,
+            "\nlr ={:#010x}\nelr={:#010x}  spsr={:#010x} ({:?})",
+            self.lr,
+            self.elr,
+            self.spsr,
+            self.mode()
+        )
+    }
+}
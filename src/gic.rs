@@ -1,58 +1,202 @@
 /// GIC (Generic Interrupt Controller) Driver for ARM GICv3
 ///
 /// This module provides initialization and control for the GICv3 interrupt controller,
-/// with support for Software Generated Interrupts (SGIs).
+/// with support for Software Generated Interrupts (SGIs), Private Peripheral Interrupts (PPIs)
+/// and Shared Peripheral Interrupts (SPIs).
 use core::{
     arch::asm,
-    ptr::{read_volatile, write_volatile},
+    fmt::Write,
+    mem::{offset_of, size_of},
+    sync::atomic::{AtomicU8, AtomicUsize, Ordering},
 };
 
-// ==================== GIC Distributor (GICD) ====================
-/// GIC Distributor base address
-pub const GICD_BASE: usize = 0xAF000000;
+use crate::mmio::Register;
+use crate::system::read_mpidr;
+use crate::uart::Uart;
+
+// ==================== GIC Memory Map ====================
+
+/// Location of a GICv3 in the physical memory map
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GicConfig {
+    /// Distributor (GICD) base address
+    pub gicd_base: usize,
+    /// Redistributor (GICR) base address, i.e. the frame of the first core
+    pub gicr_base: usize,
+}
 
-/// GICD Control Register - Enable distributor
-const GICD_CTLR: *mut u32 = (GICD_BASE + 0x0000) as *mut u32;
+impl GicConfig {
+    /// Arm FVP BaseR (AEMv8-R) memory map
+    pub const FVP_BASER: Self = Self {
+        gicd_base: 0xAF00_0000,
+        gicr_base: 0xAF10_0000,
+    };
 
-/// GICD Type Register - Provides information about the GIC configuration
-const GICD_TYPER: *mut u32 = (GICD_BASE + 0x0004) as *mut u32;
+    /// QEMU `mps3-an536` memory map
+    pub const MPS3_AN536: Self = Self {
+        gicd_base: 0xF000_0000,
+        gicr_base: 0xF010_0000,
+    };
+
+    /// Memory map of the Cortex-R52 internal GIC for a given PERIPHBASE
+    ///
+    /// The distributor sits at PERIPHBASE and the redistributors 1MB above it.
+    pub const fn from_periphbase(periphbase: usize) -> Self {
+        Self {
+            gicd_base: periphbase,
+            gicr_base: periphbase + 0x10_0000,
+        }
+    }
 
-/// GICD Interrupt Group Registers - Configure interrupt groups
-const GICD_IGROUPR0: *mut u32 = (GICD_BASE + 0x0080) as *mut u32;
+    /// Memory map of the Cortex-R52 internal GIC, read from the calling core's CBAR
+    pub fn from_cbar() -> Self {
+        let cbar: u32;
+        unsafe {
+            // CBAR (Configuration Base Address Register): CP15, 1, Rt, c15, c3, 0
+            asm!("mrc p15, 1, {}, c15, c3, 0", out(reg) cbar);
+        }
+        // PERIPHBASE is held in bits [31:21]
+        Self::from_periphbase((cbar & CBAR_PERIPHBASE_MASK) as usize)
+    }
+}
 
-/// GICD Interrupt Set-Enable Registers - Enable interrupts
-const GICD_ISENABLER0: *mut u32 = (GICD_BASE + 0x0100) as *mut u32;
+/// PERIPHBASE field of CBAR
+const CBAR_PERIPHBASE_MASK: u32 = 0xFFE0_0000;
 
-/// GICD Interrupt Clear-Enable Registers - Disable interrupts
-const GICD_ICENABLER0: *mut u32 = (GICD_BASE + 0x0180) as *mut u32;
+// ==================== GIC Distributor (GICD) ====================
 
-/// GICD Interrupt Priority Registers - Set interrupt priorities (SGI 0-15)
-const GICD_IPRIORITYR: *mut u32 = (GICD_BASE + 0x0400) as *mut u32;
+/// GIC Distributor register block
+#[repr(C)]
+pub struct GicdRegisters {
+    /// 0x0000 Control Register - Enable distributor
+    pub ctlr: Register<u32>,
+    /// 0x0004 Type Register - Provides information about the GIC configuration
+    pub typer: Register<u32>,
+    /// 0x0008 Implementer Identification Register
+    pub iidr: Register<u32>,
+    /// 0x000C Type Register 2
+    pub typer2: Register<u32>,
+    /// 0x0010 Error Reporting Status Register
+    pub statusr: Register<u32>,
+    _reserved0: [u8; 0x006C],
+    /// 0x0080 Interrupt Group Registers - Configure interrupt groups
+    pub igroupr: [Register<u32>; 32],
+    /// 0x0100 Interrupt Set-Enable Registers - Enable interrupts
+    pub isenabler: [Register<u32>; 32],
+    /// 0x0180 Interrupt Clear-Enable Registers - Disable interrupts
+    pub icenabler: [Register<u32>; 32],
+    /// 0x0200 Interrupt Set-Pending Registers - Set interrupts pending
+    pub ispendr: [Register<u32>; 32],
+    /// 0x0280 Interrupt Clear-Pending Registers - Clear pending interrupts
+    pub icpendr: [Register<u32>; 32],
+    /// 0x0300 Interrupt Set-Active Registers - Set interrupts active
+    pub isactiver: [Register<u32>; 32],
+    /// 0x0380 Interrupt Clear-Active Registers - Deactivate interrupts
+    pub icactiver: [Register<u32>; 32],
+    /// 0x0400 Interrupt Priority Registers - One byte per INTID
+    pub ipriorityr: [Register<u8>; 1024],
+    _reserved1: [u8; 0x0400],
+    /// 0x0C00 Interrupt Configuration Registers - Edge/level trigger (2 bits per INTID)
+    pub icfgr: [Register<u32>; 64],
+    /// 0x0D00 Interrupt Group Modifier Registers
+    pub igrpmodr: [Register<u32>; 32],
+    _reserved2: [u8; 0x5280],
+    /// 0x6000 Interrupt Routing Registers - SPI target affinity (64 bits per INTID,
+    /// accessed as low and high words)
+    pub irouter: [[Register<u32>; 2]; 1024],
+}
 
-/// GICD Software Generated Interrupt Register - Trigger SGIs
-const GICD_SGIR: *mut u32 = (GICD_BASE + 0x0F00) as *mut u32;
+const _: () = {
+    assert!(offset_of!(GicdRegisters, igroupr) == 0x0080);
+    assert!(offset_of!(GicdRegisters, ipriorityr) == 0x0400);
+    assert!(offset_of!(GicdRegisters, icfgr) == 0x0C00);
+    assert!(offset_of!(GicdRegisters, irouter) == 0x6000);
+};
 
 // ==================== GIC Redistributor (GICR) ====================
-/// GIC Redistributor base address
-pub const GICR_BASE: usize = 0xAF100000;
 
-/// GICR Control Register
-const GICR_CTLR: *mut u32 = (GICR_BASE + 0x0000) as *mut u32;
+/// Redistributor RD_base frame (control and power management)
+#[repr(C)]
+pub struct GicrRdFrame {
+    /// 0x0000 Control Register
+    pub ctlr: Register<u32>,
+    /// 0x0004 Implementer Identification Register
+    pub iidr: Register<u32>,
+    /// 0x0008 Type Register (64-bit) - Last frame flag in the low word,
+    /// affinity in the high word
+    pub typer: [Register<u32>; 2],
+    /// 0x0010 Error Reporting Status Register
+    pub statusr: Register<u32>,
+    /// 0x0014 Waker Register - Controls power management
+    pub waker: Register<u32>,
+    _reserved0: [u8; 0xFFE8],
+}
 
-/// GICR Waker Register - Controls power management
-const GICR_WAKER: *mut u32 = (GICR_BASE + 0x0014) as *mut u32;
+/// Redistributor SGI_base frame (the core's SGIs and PPIs, INTIDs 0-31)
+#[repr(C)]
+pub struct GicrSgiFrame {
+    _reserved0: [u8; 0x0080],
+    /// 0x0080 Interrupt Group Register 0
+    pub igroupr0: Register<u32>,
+    _reserved1: [u8; 0x007C],
+    /// 0x0100 Interrupt Set-Enable Register 0
+    pub isenabler0: Register<u32>,
+    _reserved2: [u8; 0x007C],
+    /// 0x0180 Interrupt Clear-Enable Register 0
+    pub icenabler0: Register<u32>,
+    _reserved3: [u8; 0x007C],
+    /// 0x0200 Interrupt Set-Pending Register 0
+    pub ispendr0: Register<u32>,
+    _reserved4: [u8; 0x007C],
+    /// 0x0280 Interrupt Clear-Pending Register 0
+    pub icpendr0: Register<u32>,
+    _reserved5: [u8; 0x007C],
+    /// 0x0300 Interrupt Set-Active Register 0
+    pub isactiver0: Register<u32>,
+    _reserved6: [u8; 0x007C],
+    /// 0x0380 Interrupt Clear-Active Register 0
+    pub icactiver0: Register<u32>,
+    _reserved7: [u8; 0x007C],
+    /// 0x0400 Interrupt Priority Registers - One byte per INTID
+    pub ipriorityr: [Register<u8>; 32],
+    _reserved8: [u8; 0x07E0],
+    /// 0x0C00 Interrupt Configuration Registers (ICFGR0: SGIs, read-only; ICFGR1: PPIs)
+    pub icfgr: [Register<u32>; 2],
+    _reserved9: [u8; 0x00F8],
+    /// 0x0D00 Interrupt Group Modifier Register 0
+    pub igrpmodr0: Register<u32>,
+    _reserved10: [u8; 0xF2FC],
+}
 
-/// GICR Interrupt Group Register 0 (SGIs 0-31)
-const GICR_IGROUPR0: *mut u32 = (GICR_BASE + 0x10000 + 0x0080) as *mut u32;
+/// One core's redistributor: RD_base frame followed by SGI_base frame
+#[repr(C)]
+pub struct GicrRegisters {
+    pub rd: GicrRdFrame,
+    pub sgi: GicrSgiFrame,
+}
+
+/// Size of one core's redistributor
+const GICR_FRAME_STRIDE: usize = 0x20000;
+
+const _: () = {
+    assert!(offset_of!(GicrRdFrame, waker) == 0x0014);
+    assert!(offset_of!(GicrSgiFrame, ipriorityr) == 0x0400);
+    assert!(offset_of!(GicrSgiFrame, icfgr) == 0x0C00);
+    assert!(offset_of!(GicrSgiFrame, igrpmodr0) == 0x0D00);
+    assert!(offset_of!(GicrRegisters, sgi) == 0x10000);
+    assert!(size_of::<GicrRegisters>() == GICR_FRAME_STRIDE);
+};
 
-/// GICR Interrupt Set-Enable Register 0 (SGIs 0-31)
-const GICR_ISENABLER0: *mut u32 = (GICR_BASE + 0x10000 + 0x0100) as *mut u32;
+/// Upper bound on redistributor frames walked during discovery
+const GICR_MAX_FRAMES: usize = 64;
 
-/// GICR Interrupt Clear-Enable Register 0 (SGIs 0-31)
-const GICR_ICENABLER0: *mut u32 = (GICR_BASE + 0x10000 + 0x0180) as *mut u32;
+/// Cores per Cortex-R52 cluster, one redistributor cache slot each
+pub(crate) const MAX_CORES_PER_CLUSTER: usize = 4;
 
-/// GICR Interrupt Priority Registers (SGIs 0-31)
-const GICR_IPRIORITYR: *mut u32 = (GICR_BASE + 0x10000 + 0x0400) as *mut u32;
+/// Redistributor frame address found for each core (indexed by MPIDR.Aff0),
+/// or 0 before the first lookup
+static REDISTRIBUTOR_FRAMES: [AtomicUsize; MAX_CORES_PER_CLUSTER] =
+    [const { AtomicUsize::new(0) }; MAX_CORES_PER_CLUSTER];
 
 // ==================== Constants ====================
 
@@ -62,117 +206,549 @@ const GICD_CTLR_ENABLE_GRP0: u32 = 1 << 0;
 /// Enable Group 1 interrupts
 const GICD_CTLR_ENABLE_GRP1: u32 = 1 << 1;
 
+/// Register Write Pending bit in GICD_CTLR
+const GICD_CTLR_RWP: u32 = 1 << 31;
+
+/// Register Write Pending bit in GICR_CTLR
+const GICR_CTLR_RWP: u32 = 1 << 3;
+
+/// Last redistributor frame bit in GICR_TYPER
+const GICR_TYPER_LAST: u32 = 1 << 4;
+
 /// Are We Awake bit in GICR_WAKER
 const GICR_WAKER_PROCESSOR_SLEEP: u32 = 1 << 1;
 const GICR_WAKER_CHILDREN_ASLEEP: u32 = 1 << 2;
 
-/// SGI target list filter modes
-const SGIR_TARGET_LIST_FILTER_SHIFT: u32 = 24;
-const SGIR_TARGET_LIST_FILTER_MASK: u32 = 0x3 << SGIR_TARGET_LIST_FILTER_SHIFT;
-
-/// SGI target list filter: Use target list
-pub const SGI_TARGET_LIST: u32 = 0x0 << SGIR_TARGET_LIST_FILTER_SHIFT;
+/// ICC_SGI1R field positions
+const ICC_SGI1R_AFF1_SHIFT: u32 = 16;
+const ICC_SGI1R_INTID_SHIFT: u32 = 24;
+const ICC_SGI1R_AFF2_SHIFT: u32 = 32;
+const ICC_SGI1R_RS_SHIFT: u32 = 44;
+const ICC_SGI1R_AFF3_SHIFT: u32 = 48;
 
-/// SGI target list filter: All except self
-pub const SGI_TARGET_ALL_EXCEPT_SELF: u32 = 0x1 << SGIR_TARGET_LIST_FILTER_SHIFT;
-
-/// SGI target list filter: Self only
-pub const SGI_TARGET_SELF: u32 = 0x2 << SGIR_TARGET_LIST_FILTER_SHIFT;
+/// Interrupt Routing Mode bit in ICC_SGI1R: all cores except self
+const ICC_SGI1R_IRM: u64 = 1 << 40;
 
 /// Default priority for SGIs (higher number = lower priority)
 const DEFAULT_SGI_PRIORITY: u8 = 0xA0;
 
+/// Default priority for SPIs (higher number = lower priority)
+const DEFAULT_SPI_PRIORITY: u8 = 0xA0;
+
+/// First PPI interrupt ID; IDs below this are SGIs
+pub const PPI_BASE_ID: u32 = 16;
+
+/// First SPI interrupt ID; IDs below this are banked per core (SGIs and PPIs)
+pub const SPI_BASE_ID: u32 = 32;
+
+/// First special interrupt ID (1020-1023 are reserved for spurious/special use)
+pub const SPECIAL_INTERRUPT_ID_START: u32 = 1020;
+
+/// Interrupt group assignment (GICD_IGROUPRn / GICR_IGROUPR0)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterruptGroup {
+    /// Group 0 (signalled as FIQ)
+    Group0,
+    /// Group 1 (signalled as IRQ)
+    Group1,
+}
+
+/// Interrupt Routing Mode bit in GICD_IROUTERn: route to any participating core
+const GICD_IROUTER_IRM: u32 = 1 << 31;
+
+/// Core affinity as used by MPIDR and the GIC affinity routing registers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Affinity {
+    pub aff3: u8,
+    pub aff2: u8,
+    /// Cluster number on Cortex-R52
+    pub aff1: u8,
+    /// Core number within the cluster on Cortex-R52
+    pub aff0: u8,
+}
+
+impl Affinity {
+    /// Create an affinity from its individual levels
+    pub const fn new(aff3: u8, aff2: u8, aff1: u8, aff0: u8) -> Self {
+        Self {
+            aff3,
+            aff2,
+            aff1,
+            aff0,
+        }
+    }
+
+    /// Extract the affinity levels from an AArch32 MPIDR value (Aff3 is always 0)
+    pub const fn from_mpidr(mpidr: u32) -> Self {
+        Self::new(0, (mpidr >> 16) as u8, (mpidr >> 8) as u8, mpidr as u8)
+    }
+
+    /// Affinity of the calling core
+    pub fn current() -> Self {
+        Self::from_mpidr(read_mpidr())
+    }
+
+    /// Pack the affinity levels as in GICR_TYPER[63:32] (Aff3.Aff2.Aff1.Aff0)
+    pub const fn packed(self) -> u32 {
+        (self.aff3 as u32) << 24
+            | (self.aff2 as u32) << 16
+            | (self.aff1 as u32) << 8
+            | self.aff0 as u32
+    }
+}
+
+impl core::fmt::Display for Affinity {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}.{}.{}.{}", self.aff3, self.aff2, self.aff1, self.aff0)
+    }
+}
+
+/// SPI routing target (GICD_IROUTERn)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpiRoute {
+    /// Deliver to the core with this affinity
+    Core(Affinity),
+    /// Deliver to any one participating core (1 of N)
+    AnyParticipating,
+}
+
+/// EOImode bit in ICC_CTLR: EOIR only drops priority, ICC_DIR deactivates
+const ICC_CTLR_EOIMODE: u32 = 1 << 1;
+
+/// End-of-interrupt behaviour of the CPU interface (ICC_CTLR.EOImode)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EoiMode {
+    /// EOImode = 0: an EOIR write drops the running priority and deactivates
+    DropAndDeactivate,
+    /// EOImode = 1: an EOIR write only drops the running priority; the
+    /// interrupt stays active until written to ICC_DIR
+    DropOnly,
+}
+
+/// SGI target (ICC_SGI1R)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SgiTarget {
+    /// Cores in cluster `aff3.aff2.aff1` whose Aff0 is `range * 16 + n`
+    /// for every bit `n` set in `target_list`
+    List {
+        aff3: u8,
+        aff2: u8,
+        aff1: u8,
+        /// Range selector (RS), 0-15
+        range: u8,
+        target_list: u16,
+    },
+    /// All participating cores except the sender (IRM = 1)
+    AllExceptSelf,
+}
+
+impl SgiTarget {
+    /// Target exactly one core
+    pub const fn core(affinity: Affinity) -> Self {
+        SgiTarget::List {
+            aff3: affinity.aff3,
+            aff2: affinity.aff2,
+            aff1: affinity.aff1,
+            range: affinity.aff0 / 16,
+            target_list: 1 << (affinity.aff0 % 16),
+        }
+    }
+
+    /// Target the calling core, as identified by its MPIDR
+    pub fn current_core() -> Self {
+        Self::core(Affinity::current())
+    }
+}
+
+/// Target filter for [`Gic::send_sgi`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SgiFilter {
+    /// The cores in the caller's cluster selected by the target list
+    TargetList,
+    /// All participating cores except the sender
+    AllExceptSelf,
+    /// The sender only
+    SelfOnly,
+}
+
+/// Interrupt trigger mode (GICD_ICFGRn / GICR_ICFGR1)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriggerMode {
+    /// Level-sensitive: pending while the input is asserted
+    Level,
+    /// Edge-triggered: pending on a rising edge of the input
+    Edge,
+}
+
+/// GIC driver errors
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GicError {
+    /// SGI number outside 0-15
+    InvalidSgi(u32),
+    /// PPI number outside 16-31
+    InvalidPpi(u32),
+    /// SPI number outside 32-1019
+    InvalidSpi(u32),
+    /// Special interrupt ID (1020-1023) used where a real interrupt is required
+    SpecialInterrupt(u32),
+    /// Interrupt ID beyond the lines implemented by this GIC (GICD_TYPER.ITLinesNumber)
+    NotImplemented(u32),
+    /// SGIs are always edge-triggered and cannot be made level-sensitive
+    SgiTriggerFixed(u32),
+    /// The implementation ignored a trigger mode change for this interrupt
+    TriggerNotConfigurable(u32),
+    /// No redistributor frame matches this core's affinity (Aff3.Aff2.Aff1.Aff0)
+    NoRedistributor(u32),
+}
+
+/// Software Generated Interrupt ID (0-15)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SgiId(u8);
+
+impl SgiId {
+    /// Create an SGI ID, checking the architectural range 0-15
+    pub const fn new(id: u8) -> Result<Self, GicError> {
+        if id < PPI_BASE_ID as u8 {
+            Ok(Self(id))
+        } else {
+            Err(GicError::InvalidSgi(id as u32))
+        }
+    }
+
+    /// Create an SGI ID from a constant
+    ///
+    /// # Panics
+    /// If `id` is out of range; in a `const` item this is a compile-time error
+    pub const fn new_const(id: u8) -> Self {
+        match Self::new(id) {
+            Ok(sgi) => sgi,
+            Err(_) => panic!("SGI ID out of range (0-15)"),
+        }
+    }
+
+    /// SGI number (0-15)
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
+
+/// Private Peripheral Interrupt ID (16-31)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PpiId(u8);
+
+impl PpiId {
+    /// Create a PPI ID, checking the architectural range 16-31
+    pub const fn new(id: u8) -> Result<Self, GicError> {
+        if id >= PPI_BASE_ID as u8 && id < SPI_BASE_ID as u8 {
+            Ok(Self(id))
+        } else {
+            Err(GicError::InvalidPpi(id as u32))
+        }
+    }
+
+    /// Create a PPI ID from a constant
+    ///
+    /// # Panics
+    /// If `id` is out of range; in a `const` item this is a compile-time error
+    pub const fn new_const(id: u8) -> Self {
+        match Self::new(id) {
+            Ok(ppi) => ppi,
+            Err(_) => panic!("PPI ID out of range (16-31)"),
+        }
+    }
+
+    /// Interrupt ID (16-31)
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
+
+/// Shared Peripheral Interrupt ID (32-1019)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpiId(u16);
+
+impl SpiId {
+    /// Create an SPI ID, checking the architectural range 32-1019
+    ///
+    /// Use [`Gic::spi_id`] to also check it against the lines implemented by a
+    /// particular GIC; operations on an SPI the GIC does not implement return
+    /// `GicError::NotImplemented`.
+    pub const fn new(id: u32) -> Result<Self, GicError> {
+        if id >= SPI_BASE_ID && id < SPECIAL_INTERRUPT_ID_START {
+            Ok(Self(id as u16))
+        } else {
+            Err(GicError::InvalidSpi(id))
+        }
+    }
+
+    /// Create an SPI ID from a constant
+    ///
+    /// # Panics
+    /// If `id` is out of range; in a `const` item this is a compile-time error
+    pub const fn new_const(id: u32) -> Self {
+        match Self::new(id) {
+            Ok(spi) => spi,
+            Err(_) => panic!("SPI ID out of range (32-1019)"),
+        }
+    }
+
+    /// Interrupt ID (32-1019)
+    pub const fn value(self) -> u32 {
+        self.0 as u32
+    }
+}
+
+/// Any non-special interrupt ID (SGI, PPI or SPI; 0-1019)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IntId(u16);
+
+impl IntId {
+    /// Create an interrupt ID, rejecting the special IDs 1020-1023 and above
+    pub const fn new(id: u32) -> Result<Self, GicError> {
+        if id < SPECIAL_INTERRUPT_ID_START {
+            Ok(Self(id as u16))
+        } else {
+            Err(GicError::SpecialInterrupt(id))
+        }
+    }
+
+    /// Interrupt ID (0-1019)
+    pub const fn value(self) -> u32 {
+        self.0 as u32
+    }
+
+    /// Whether this is an SGI (0-15)
+    pub const fn is_sgi(self) -> bool {
+        self.value() < PPI_BASE_ID
+    }
+
+    /// Whether this is a PPI (16-31)
+    pub const fn is_ppi(self) -> bool {
+        self.value() >= PPI_BASE_ID && self.value() < SPI_BASE_ID
+    }
+
+    /// Whether this is an SPI (32-1019)
+    pub const fn is_spi(self) -> bool {
+        self.value() >= SPI_BASE_ID
+    }
+}
+
+impl From<SgiId> for IntId {
+    fn from(sgi: SgiId) -> Self {
+        Self(sgi.0 as u16)
+    }
+}
+
+impl From<PpiId> for IntId {
+    fn from(ppi: PpiId) -> Self {
+        Self(ppi.0 as u16)
+    }
+}
+
+impl From<SpiId> for IntId {
+    fn from(spi: SpiId) -> Self {
+        Self(spi.0)
+    }
+}
+
+/// Distributor configuration state, shared by all cores
+static DISTRIBUTOR_STATE: AtomicU8 = AtomicU8::new(DISTRIBUTOR_UNINITIALIZED);
+const DISTRIBUTOR_UNINITIALIZED: u8 = 0;
+/// A core has claimed the distributor and is configuring it
+const DISTRIBUTOR_IN_PROGRESS: u8 = 1;
+/// GICD_CTLR is enabled and the configuration is visible to every core
+const DISTRIBUTOR_DONE: u8 = 2;
+
+/// One-bit-per-interrupt register banks, present in both the distributor
+/// (SPIs) and the redistributor SGI frame (SGIs and PPIs)
+#[derive(Clone, Copy)]
+enum BitBank {
+    Group,
+    SetEnable,
+    ClearEnable,
+    SetPending,
+    ClearPending,
+    SetActive,
+    ClearActive,
+}
+
 // ==================== GIC Structure ====================
 
 /// GIC (Generic Interrupt Controller) driver structure
-pub struct Gic;
+///
+/// An instance is bound to one distributor and its redistributors. The CPU
+/// interface is accessed through system registers and is the same on every
+/// platform, so its functions are associated functions that need no instance.
+#[derive(Clone, Copy, Debug)]
+pub struct Gic {
+    gicd: *const GicdRegisters,
+    gicr: *const GicrRegisters,
+}
+
+// The register blocks are shared hardware; the driver holds no other state
+unsafe impl Send for Gic {}
+unsafe impl Sync for Gic {}
 
 impl Gic {
-    /// Initialize the GIC distributor and redistributor
+    /// Create a driver for the GIC at the given addresses
+    ///
+    /// # Safety
+    /// `config` must describe a GICv3 distributor and redistributor range
+    /// actually present in the memory map.
+    pub const unsafe fn new(config: GicConfig) -> Self {
+        Self {
+            gicd: config.gicd_base as *const GicdRegisters,
+            gicr: config.gicr_base as *const GicrRegisters,
+        }
+    }
+
+    /// Create a driver for the Cortex-R52 internal GIC, located through CBAR
+    pub fn from_cbar() -> Self {
+        unsafe { Self::new(GicConfig::from_cbar()) }
+    }
+
+    /// Addresses this driver was created with
+    pub fn config(&self) -> GicConfig {
+        GicConfig {
+            gicd_base: self.gicd as usize,
+            gicr_base: self.gicr as usize,
+        }
+    }
+
+    /// Distributor register block
+    pub fn distributor(&self) -> &GicdRegisters {
+        unsafe { &*self.gicd }
+    }
+
+    /// Initialize the GIC distributor, this core's redistributor and CPU interface
+    ///
+    /// This function:
+    /// 1. Configures the distributor (first caller only, see `init_distributor`)
+    /// 2. Wakes up and configures the calling core's redistributor
+    /// 3. Enables the calling core's CPU interface
+    ///
+    /// Every core that takes interrupts must call this (or `init_cpu`) itself.
+    ///
+    /// # Returns
+    /// `GicError::NoRedistributor` if this core has no redistributor frame
+    pub fn init(&self) -> Result<(), GicError> {
+        self.init_distributor();
+        self.init_cpu()
+    }
+
+    /// Initialize the GIC distributor
     ///
     /// This function:
     /// 1. Disables the distributor
-    /// 2. Configures interrupt groups
-    /// 3. Wakes up the redistributor
-    /// 4. Enables the distributor for both Group 0 and Group 1
-    pub fn init() {
-        unsafe {
-            // ===== Initialize Distributor =====
-
-            // Disable distributor while configuring
-            write_volatile(GICD_CTLR, 0);
-
-            // Configure SGIs (0-15) as Group 1 interrupts
-            // Setting bit = Group 1, Clearing bit = Group 0
-            let mut igroupr = read_volatile(GICD_IGROUPR0);
-            igroupr |= 0xFFFF_FFFF; // SGIs 0-15 as Group 1
-            write_volatile(GICD_IGROUPR0, igroupr);
-
-            // Set default priority for SGIs (0-15)
-            for i in 0..4 {
-                // Each register holds 4 priorities (8 bits each)
-                let priority_reg = GICD_IPRIORITYR.add(i);
-                write_volatile(
-                    priority_reg,
-                    (DEFAULT_SGI_PRIORITY as u32) << 24
-                        | (DEFAULT_SGI_PRIORITY as u32) << 16
-                        | (DEFAULT_SGI_PRIORITY as u32) << 8
-                        | (DEFAULT_SGI_PRIORITY as u32),
-                );
+    /// 2. Configures interrupt groups and priorities for SGIs and SPIs
+    /// 3. Enables the distributor for both Group 0 and Group 1
+    ///
+    /// The distributor is shared by all cores, so only the first call has any
+    /// effect. Other cores wait until that call has enabled the distributor, so
+    /// SPI settings they make afterwards are not overwritten.
+    pub fn init_distributor(&self) {
+        if DISTRIBUTOR_STATE
+            .compare_exchange(
+                DISTRIBUTOR_UNINITIALIZED,
+                DISTRIBUTOR_IN_PROGRESS,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            )
+            .is_err()
+        {
+            while DISTRIBUTOR_STATE.load(Ordering::Acquire) != DISTRIBUTOR_DONE {
+                core::hint::spin_loop();
             }
+            return;
+        }
 
-            // ===== Initialize Redistributor =====
+        let gicd = self.distributor();
 
-            // Wake up the redistributor
-            let mut waker = read_volatile(GICR_WAKER);
-            waker &= !GICR_WAKER_PROCESSOR_SLEEP;
-            write_volatile(GICR_WAKER, waker);
+        // ===== Initialize Distributor =====
 
-            // Wait for children to wake up
-            while (read_volatile(GICR_WAKER) & GICR_WAKER_CHILDREN_ASLEEP) != 0 {
-                // Spin wait
-            }
+        // Disable distributor while configuring
+        gicd.ctlr.write(0);
 
-            // Configure SGIs (0-15) as Group 1 in redistributor
-            let mut gicr_igroupr = read_volatile(GICR_IGROUPR0);
-            gicr_igroupr |= 0xFFFF_FFFF; // SGIs 0-15 as Group 1
-            write_volatile(GICR_IGROUPR0, gicr_igroupr);
-
-            // Enable all SGIs (0-15) in redistributor
-            write_volatile(GICR_ISENABLER0, 0xFFFF_FFFF);
-
-            // Set default priority for SGIs in redistributor
-            for i in 0..4 {
-                let priority_reg = GICR_IPRIORITYR.add(i);
-                write_volatile(
-                    priority_reg,
-                    (DEFAULT_SGI_PRIORITY as u32) << 24
-                        | (DEFAULT_SGI_PRIORITY as u32) << 16
-                        | (DEFAULT_SGI_PRIORITY as u32) << 8
-                        | (DEFAULT_SGI_PRIORITY as u32),
-                );
-            }
+        // Configure SGIs (0-15) as Group 1 interrupts
+        // Setting bit = Group 1, Clearing bit = Group 0
+        gicd.igroupr[0].modify(|igroupr| igroupr | 0xFFFF_FFFF); // SGIs 0-15 as Group 1
+
+        // Set default priority for SGIs (0-15)
+        for priority in &gicd.ipriorityr[..PPI_BASE_ID as usize] {
+            priority.write(DEFAULT_SGI_PRIORITY);
+        }
+
+        // Configure all implemented SPIs: disabled, Group 1, default priority
+        let num_interrupts = self.get_num_interrupts().min(SPECIAL_INTERRUPT_ID_START);
+        for bank in (SPI_BASE_ID / 32)..(num_interrupts.div_ceil(32)) {
+            gicd.icenabler[bank as usize].write(0xFFFF_FFFF);
+            gicd.igroupr[bank as usize].write(0xFFFF_FFFF);
+        }
+        self.wait_for_distributor_rwp();
+
+        for priority in &gicd.ipriorityr[SPI_BASE_ID as usize..num_interrupts as usize] {
+            priority.write(DEFAULT_SPI_PRIORITY);
+        }
+
+        // ===== Enable Distributor =====
+
+        // Enable distributor for both Group 0 and Group 1
+        gicd.ctlr
+            .write(GICD_CTLR_ENABLE_GRP0 | GICD_CTLR_ENABLE_GRP1);
+        self.wait_for_distributor_rwp();
+
+        DISTRIBUTOR_STATE.store(DISTRIBUTOR_DONE, Ordering::Release);
+    }
+
+    /// Initialize the calling core's redistributor and CPU interface
+    ///
+    /// This function:
+    /// 1. Locates the redistributor frame matching this core's MPIDR
+    /// 2. Wakes up the redistributor
+    /// 3. Configures and enables this core's SGIs and the timer PPI
+    /// 4. Enables the CPU interface for Group 0 (FIQ) and Group 1 (IRQ) interrupts
+    ///
+    /// # Returns
+    /// `GicError::NoRedistributor` if this core has no redistributor frame
+    pub fn init_cpu(&self) -> Result<(), GicError> {
+        let gicr = self.redistributor()?;
+
+        // ===== Initialize Redistributor =====
+
+        // Wake up the redistributor
+        gicr.rd
+            .waker
+            .modify(|waker| waker & !GICR_WAKER_PROCESSOR_SLEEP);
+
+        // Wait for children to wake up
+        while (gicr.rd.waker.read() & GICR_WAKER_CHILDREN_ASLEEP) != 0 {
+            // Spin wait
+        }
+
+        // Configure SGIs (0-15) as Group 1 in redistributor
+        gicr.sgi.igroupr0.modify(|igroupr| igroupr | 0xFFFF_FFFF); // SGIs 0-15 as Group 1
+
+        // Enable all SGIs (0-15) in redistributor
+        gicr.sgi.isenabler0.write(0xFFFF_FFFF);
+
+        // Set default priority for SGIs in redistributor
+        for priority in &gicr.sgi.ipriorityr[..PPI_BASE_ID as usize] {
+            priority.write(DEFAULT_SGI_PRIORITY);
+        }
+
+        // Enable timer interrupts in redistributor
+        // (Assuming timer interrupt ID is 30 for Cortex-R52)
+        let timer_interrupt_id: u8 = 30;
+        let timer_mask = 1u32 << (timer_interrupt_id % 32);
+        gicr.sgi.isenabler0.write(timer_mask);
+
+        // Set timer interrupt as Group 1
+        gicr.sgi.igroupr0.modify(|igroupr| igroupr | timer_mask);
 
-            // Enable timer interrupts in redistributor
-            // (Assuming timer interrupt ID is 30 for Cortex-R52)
-            let timer_interrupt_id: u8 = 30;
-            let timer_mask = 1u32 << (timer_interrupt_id % 32);
-            write_volatile(GICR_ISENABLER0, timer_mask);
-
-            // Set timer interrupt as Group 1
-            let mut gicr_igroupr_timer = read_volatile(GICR_IGROUPR0);
-            gicr_igroupr_timer |= timer_mask;
-            write_volatile(GICR_IGROUPR0, gicr_igroupr_timer);
-
-            // Set default priority for timer interrupt
-            let timer_priority_reg = GICR_IPRIORITYR.add((timer_interrupt_id / 4) as usize);
-            let byte_offset = (timer_interrupt_id % 4) * 8;
-            let mut current_priority = read_volatile(timer_priority_reg);
-            let mask = 0xFF << byte_offset;
-            current_priority =
-                (current_priority & !mask) | ((DEFAULT_SGI_PRIORITY as u32) << byte_offset);
-            write_volatile(timer_priority_reg, current_priority);
+        // Set default priority for timer interrupt
+        gicr.sgi.ipriorityr[timer_interrupt_id as usize].write(DEFAULT_SGI_PRIORITY);
 
+        unsafe {
             Self::write_icc_ctlr(0); // Enable GIC CPU interface
 
             // ICC_PMR_EL1 - Set priority mask to allow all priorities
@@ -203,13 +779,12 @@ impl Gic {
             asm!("mcr p15, 0, {}, c12, c12, 7", in(reg) 1u32);
             asm!("isb");
 
-            // ===== Enable Distributor =====
+            // 4. Enable Group 0 Interrupts (delivered as FIQ) in CPU Interface
+            Self::write_icc_igrpen0(true);
 
             Self::write_icc_ctlr(0); // EOImode = 0
-
-            // Enable distributor for both Group 0 and Group 1
-            write_volatile(GICD_CTLR, GICD_CTLR_ENABLE_GRP0 | GICD_CTLR_ENABLE_GRP1);
         }
+        Ok(())
     }
 
     /// Read ICC_RPR (Running Priority Register)
@@ -238,22 +813,74 @@ impl Gic {
         hppir
     }
 
-    /// Enable a specific SGI (Software Generated Interrupt)
+    /// Read ICC_HPPIR0 (Highest Priority Pending Interrupt - Group 0)
+    pub fn read_highest_pending_group0() -> u32 {
+        let hppir: u32;
+        unsafe {
+            // ICC_HPPIR0: CP15, 0, Rt, c12, c8, 2
+            asm!(
+                "mrc p15, 0, {}, c12, c8, 2",
+                out(reg) hppir
+            );
+        }
+        hppir
+    }
+
+    /// Read ICC_PMR (Priority Mask Register)
+    /// Only interrupts with a priority value below the mask are signalled
+    pub fn read_priority_mask() -> u32 {
+        let pmr: u32;
+        unsafe {
+            // ICC_PMR: CP15, 0, Rt, c4, c6, 0
+            asm!("mrc p15, 0, {}, c4, c6, 0", out(reg) pmr);
+        }
+        pmr
+    }
+
+    /// Write ICC_PMR (Priority Mask Register)
+    /// Interrupts with a priority value equal to or above the mask stay pending
     ///
     /// # Arguments
-    /// * `sgi_id` - SGI number (0-15)
-    pub fn enable_sgi(sgi_id: u8) {
-        if sgi_id > 15 {
-            return; // SGIs are only 0-15
+    /// * `value` - Mask value (0xFF lets every priority through)
+    pub fn write_priority_mask(value: u32) {
+        unsafe {
+            // ICC_PMR: CP15, 0, Rt, c4, c6, 0
+            asm!("mcr p15, 0, {}, c4, c6, 0", in(reg) value);
+            asm!("isb");
         }
+    }
 
+    /// Read ICC_IGRPEN0 (Interrupt Group 0 Enable)
+    pub fn read_icc_igrpen0() -> u32 {
+        let value: u32;
         unsafe {
-            let mask = 1u32 << sgi_id;
+            asm!("mrc p15, 0, {}, c12, c12, 6", out(reg) value);
+        }
+        value
+    }
 
-            // Enable in GICR
-            let current = read_volatile(GICR_ISENABLER0);
-            write_volatile(GICR_ISENABLER0, current | mask);
+    /// Read ICC_IGRPEN1 (Interrupt Group 1 Enable)
+    pub fn read_icc_igrpen1() -> u32 {
+        let value: u32;
+        unsafe {
+            asm!("mrc p15, 0, {}, c12, c12, 7", out(reg) value);
         }
+        value
+    }
+
+    /// Enable a specific SGI (Software Generated Interrupt)
+    ///
+    /// # Arguments
+    /// * `sgi_id` - SGI number
+    pub fn enable_sgi(&self, sgi_id: SgiId) -> Result<(), GicError> {
+        let mask = 1u32 << sgi_id.value();
+
+        // Enable in GICR
+        self.redistributor()?
+            .sgi
+            .isenabler0
+            .modify(|current| current | mask);
+        Ok(())
     }
 
     /// Read ICC_IAR1 (Interrupt Acknowledge Register - Group 1)
@@ -270,20 +897,32 @@ impl Gic {
         iar
     }
 
+    /// Read ICC_IAR0 (Interrupt Acknowledge Register - Group 0)
+    /// Acknowledges the highest priority pending Group 0 (FIQ) interrupt
+    pub fn read_interrupt_ack_group0() -> u32 {
+        let iar: u32;
+        unsafe {
+            // ICC_IAR0: CP15, opc1=0, Rt, CRn=c12, CRm=c8, opc2=0
+            asm!(
+                "mrc p15, 0, {}, c12, c8, 0",
+                out(reg) iar
+            );
+        }
+        iar
+    }
+
     /// Extract just the interrupt ID from an IAR value
     pub fn get_interrupt_id(iar: u32) -> u32 {
         iar & 0x3FF // Bits [9:0] contain the interrupt ID
     }
 
     /// Clear pending SGI by writing to GICR_ICPENDR0
-    pub fn clear_sgi_pending(sgi_id: u8) {
-        if sgi_id > 15 {
-            return;
-        }
-        unsafe {
-            let icpendr0 = (GICR_BASE + 0x10000 + 0x0280) as *mut u32;
-            write_volatile(icpendr0, 1u32 << sgi_id);
-        }
+    pub fn clear_sgi_pending(&self, sgi_id: SgiId) -> Result<(), GicError> {
+        self.redistributor()?
+            .sgi
+            .icpendr0
+            .write(1u32 << sgi_id.value());
+        Ok(())
     }
 
     /// Write ICC_DIR (Deactivate Interrupt Register)
@@ -323,202 +962,767 @@ impl Gic {
         }
     }
 
-    pub fn write_icc_ctlr(value: u32) {
+    /// Write ICC_IGRPEN0 (Interrupt Group 0 Enable)
+    /// Group 0 interrupts are signalled to this core as FIQ while enabled
+    pub fn write_icc_igrpen0(enable: bool) {
         unsafe {
-            asm!("mcr p15, 0, {}, c12, c12, 4", in(reg) value);
+            // ICC_IGRPEN0: CP15, opc1=0, Rt, CRn=c12, CRm=c12, opc2=6
+            asm!("mcr p15, 0, {}, c12, c12, 6", in(reg) enable as u32);
             asm!("isb");
         }
     }
 
-    /// Disable a specific SGI
+    /// Write ICC_BPR1 (Binary Point Register - Group 1)
+    ///
+    /// Priority bits below the binary point are ignored when deciding whether
+    /// a pending interrupt may preempt the running one (nested IRQs).
     ///
     /// # Arguments
-    /// * `sgi_id` - SGI number (0-15)
-    pub fn disable_sgi(sgi_id: u8) {
-        if sgi_id > 15 {
-            return; // SGIs are only 0-15
+    /// * `value` - Binary point (0-7); group priority is priority bits [7:value+1]
+    pub fn write_binary_point_group1(value: u32) {
+        unsafe {
+            // ICC_BPR1: CP15, opc1=0, Rt, CRn=c12, CRm=c12, opc2=3
+            asm!("mcr p15, 0, {}, c12, c12, 3", in(reg) value & 0x7);
+            asm!("isb");
         }
+    }
 
+    /// Read ICC_BPR1 (Binary Point Register - Group 1)
+    pub fn read_binary_point_group1() -> u32 {
+        let value: u32;
         unsafe {
-            let mask = 1u32 << sgi_id;
+            asm!("mrc p15, 0, {}, c12, c12, 3", out(reg) value);
+        }
+        value & 0x7
+    }
 
-            // Disable in GICR
-            write_volatile(GICR_ICENABLER0, mask);
+    /// Write ICC_BPR0 (Binary Point Register - Group 0)
+    ///
+    /// # Arguments
+    /// * `value` - Binary point (0-7); group priority is priority bits [7:value+1]
+    pub fn write_binary_point_group0(value: u32) {
+        unsafe {
+            // ICC_BPR0: CP15, opc1=0, Rt, CRn=c12, CRm=c8, opc2=3
+            asm!("mcr p15, 0, {}, c12, c8, 3", in(reg) value & 0x7);
+            asm!("isb");
         }
     }
 
-    // Clear pending state of a specific SGI
-    pub fn clear_pending_sgi(sgi_id: u8) {
-        if sgi_id > 15 {
-            return; // SGIs are only 0-15
+    /// Read ICC_BPR0 (Binary Point Register - Group 0)
+    pub fn read_binary_point_group0() -> u32 {
+        let value: u32;
+        unsafe {
+            asm!("mrc p15, 0, {}, c12, c8, 3", out(reg) value);
         }
+        value & 0x7
+    }
+
+    /// Read ICC_CTLR (Interrupt Controller Control Register)
+    pub fn read_icc_ctlr() -> u32 {
+        let value: u32;
         unsafe {
-            let mask = 1u32 << sgi_id;
+            asm!("mrc p15, 0, {}, c12, c12, 4", out(reg) value);
+        }
+        value
+    }
+
+    /// Select how ICC_EOIR0/ICC_EOIR1 writes complete an interrupt (ICC_CTLR.EOImode)
+    ///
+    /// # Arguments
+    /// * `mode` - End-of-interrupt mode for this core's CPU interface
+    pub fn set_eoi_mode(mode: EoiMode) {
+        let ctlr = Self::read_icc_ctlr();
+        let ctlr = match mode {
+            EoiMode::DropAndDeactivate => ctlr & !ICC_CTLR_EOIMODE,
+            EoiMode::DropOnly => ctlr | ICC_CTLR_EOIMODE,
+        };
+        Self::write_icc_ctlr(ctlr);
+    }
+
+    /// Read the current end-of-interrupt mode of this core's CPU interface
+    pub fn get_eoi_mode() -> EoiMode {
+        if Self::read_icc_ctlr() & ICC_CTLR_EOIMODE != 0 {
+            EoiMode::DropOnly
+        } else {
+            EoiMode::DropAndDeactivate
+        }
+    }
 
-            // Clear pending in GICR
-            write_volatile(GICR_ICENABLER0, mask);
+    pub fn write_icc_ctlr(value: u32) {
+        unsafe {
+            asm!("mcr p15, 0, {}, c12, c12, 4", in(reg) value);
+            asm!("isb");
+        }
+    }
+
+    /// Disable a specific SGI
+    ///
+    /// # Arguments
+    /// * `sgi_id` - SGI number
+    pub fn disable_sgi(&self, sgi_id: SgiId) -> Result<(), GicError> {
+        let mask = 1u32 << sgi_id.value();
+
+        // Disable in GICR
+        self.redistributor()?.sgi.icenabler0.write(mask);
+        Ok(())
+    }
+
+    // Clear pending state of a specific SGI
+    pub fn clear_pending_sgi(&self, sgi_id: SgiId) -> Result<(), GicError> {
+        let mask = 1u32 << sgi_id.value();
+
+        // Clear pending in GICR
+        self.redistributor()?.sgi.icpendr0.write(mask);
+        Ok(())
+    }
+
+    /// Encode an ICC_SGI1R value
+    ///
+    /// Layout: [55:48] Aff3, [47:44] RS, [40] IRM, [39:32] Aff2,
+    /// [27:24] INTID, [23:16] Aff1, [15:0] TargetList
+    ///
+    /// # Arguments
+    /// * `sgi_id` - SGI number
+    /// * `target` - Cores to interrupt
+    pub const fn encode_sgi1r(sgi_id: SgiId, target: SgiTarget) -> u64 {
+        let intid = (sgi_id.value() as u64) << ICC_SGI1R_INTID_SHIFT;
+        match target {
+            SgiTarget::List {
+                aff3,
+                aff2,
+                aff1,
+                range,
+                target_list,
+            } => {
+                intid
+                    | (aff3 as u64) << ICC_SGI1R_AFF3_SHIFT
+                    | ((range & 0xF) as u64) << ICC_SGI1R_RS_SHIFT
+                    | (aff2 as u64) << ICC_SGI1R_AFF2_SHIFT
+                    | (aff1 as u64) << ICC_SGI1R_AFF1_SHIFT
+                    | target_list as u64
+            }
+            SgiTarget::AllExceptSelf => intid | ICC_SGI1R_IRM,
+        }
+    }
+
+    /// Send a Software Generated Interrupt (SGI) to an explicit target
+    ///
+    /// # Arguments
+    /// * `sgi_id` - SGI number
+    /// * `target` - Cores to interrupt
+    ///
+    /// # Examples
+    /// ```
+    /// // Send SGI 3 to core 1 of cluster 0
+    /// const WAKE: SgiId = SgiId::new_const(3);
+    /// Gic::send_sgi_to_target(WAKE, SgiTarget::core(Affinity::new(0, 0, 0, 1)));
+    /// ```
+    pub fn send_sgi_to_target(sgi_id: SgiId, target: SgiTarget) {
+        let value = Self::encode_sgi1r(sgi_id, target);
+        unsafe {
+            // ICC_SGI1R is a 64-bit register accessed via MCRR: CP15, 0, Rt, Rt2, c12
+            asm!(
+                "mcrr p15, 0, {}, {}, c12",
+                in(reg) value as u32,         // Lower 32 bits (TargetList, Aff1, INTID)
+                in(reg) (value >> 32) as u32  // Upper 32 bits (Aff2, IRM, RS, Aff3)
+            );
+            asm!("isb");
         }
     }
 
     /// Send a Software Generated Interrupt (SGI)
     ///
     /// # Arguments
-    /// * `sgi_id` - SGI number (0-15)
-    /// * `target_list` - Target CPU list (bit mask, bit 0 = CPU 0, etc.)
-    /// * `filter` - Target filter mode (SGI_TARGET_LIST, SGI_TARGET_ALL_EXCEPT_SELF, or SGI_TARGET_SELF)
+    /// * `sgi_id` - SGI number
+    /// * `target_list` - Target CPU list within the caller's cluster (bit mask, bit 0 = CPU 0, etc.)
+    /// * `filter` - Target filter; `target_list` is ignored unless it is `SgiFilter::TargetList`
     ///
     /// # Examples
     /// ```
     /// // Send SGI 0 to CPU 1
-    /// Gic::send_sgi(0, 0b10, SGI_TARGET_LIST);
+    /// Gic::send_sgi(SgiId::new_const(0), 0b10, SgiFilter::TargetList);
     ///
     /// // Send SGI 1 to all CPUs except self
-    /// Gic::send_sgi(1, 0, SGI_TARGET_ALL_EXCEPT_SELF);
+    /// Gic::send_sgi(SgiId::new_const(1), 0, SgiFilter::AllExceptSelf);
     ///
     /// // Send SGI 2 to self
-    /// Gic::send_sgi(2, 0, SGI_TARGET_SELF);
+    /// Gic::send_sgi(SgiId::new_const(2), 0, SgiFilter::SelfOnly);
     /// ```
+    pub fn send_sgi(sgi_id: SgiId, target_list: u16, filter: SgiFilter) {
+        let target = match filter {
+            SgiFilter::AllExceptSelf => SgiTarget::AllExceptSelf,
+            SgiFilter::SelfOnly => SgiTarget::current_core(),
+            SgiFilter::TargetList => {
+                let own = Affinity::current();
+                SgiTarget::List {
+                    aff3: own.aff3,
+                    aff2: own.aff2,
+                    aff1: own.aff1,
+                    range: 0,
+                    target_list,
+                }
+            }
+        };
+        Self::send_sgi_to_target(sgi_id, target);
+    }
 
-    pub fn send_sgi(sgi_id: u8, target_list: u16, filter: u32) {
-        if sgi_id > 15 {
-            return;
+    /// Send SGI to a specific CPU in the caller's cluster
+    ///
+    /// # Arguments
+    /// * `sgi_id` - SGI number
+    /// * `cpu_id` - Target CPU ID (MPIDR Aff0)
+    pub fn send_sgi_to_cpu(sgi_id: SgiId, cpu_id: u8) {
+        let target = Affinity {
+            aff0: cpu_id,
+            ..Affinity::current()
+        };
+        Self::send_sgi_to_target(sgi_id, SgiTarget::core(target));
+    }
+
+    /// Send SGI to all CPUs except the current one
+    ///
+    /// # Arguments
+    /// * `sgi_id` - SGI number
+    pub fn send_sgi_to_all_except_self(sgi_id: SgiId) {
+        Self::send_sgi_to_target(sgi_id, SgiTarget::AllExceptSelf);
+    }
+
+    /// Send SGI to self
+    ///
+    /// The target is derived from this core's MPIDR.
+    ///
+    /// # Arguments
+    /// * `sgi_id` - SGI number
+    pub fn send_sgi_to_self(sgi_id: SgiId) {
+        Self::send_sgi_to_target(sgi_id, SgiTarget::current_core());
+    }
+
+    /// Set the priority of an SGI
+    ///
+    /// # Arguments
+    /// * `sgi_id` - SGI number
+    /// * `priority` - Priority value (0-255, lower value = higher priority)
+    pub fn set_sgi_priority(&self, sgi_id: SgiId, priority: u8) -> Result<(), GicError> {
+        // Priority registers are byte-accessible, one byte per INTID
+        self.redistributor()?.sgi.ipriorityr[sgi_id.value() as usize].write(priority);
+        Ok(())
+    }
+
+    /// Read the GIC Distributor Type Register
+    /// Returns information about the GIC configuration
+    pub fn read_typer(&self) -> u32 {
+        self.distributor().typer.read()
+    }
+
+    /// Get the number of implemented interrupt lines
+    pub fn get_num_interrupts(&self) -> u32 {
+        let typer = self.read_typer();
+        // ITLinesNumber field [4:0] indicates (N+1)*32 interrupts
+        let it_lines = typer & 0x1F;
+        (it_lines + 1) * 32
+    }
+
+    /// Create an SPI ID, checking it against the lines implemented by this GIC
+    pub fn spi_id(&self, id: u32) -> Result<SpiId, GicError> {
+        let spi = SpiId::new(id)?;
+        self.check_implemented(spi.into())?;
+        Ok(spi)
+    }
+
+    /// Find the redistributor belonging to the core with the given affinity
+    ///
+    /// Walks the redistributor frames from the configured GICR base, comparing
+    /// the affinity in each GICR_TYPER until the frame flagged as Last.
+    ///
+    /// # Returns
+    /// The matching redistributor, or `None` if no frame matches
+    pub fn find_redistributor(&self, affinity: Affinity) -> Option<&GicrRegisters> {
+        let wanted = affinity.packed();
+
+        for frame in 0..GICR_MAX_FRAMES {
+            let gicr = unsafe { &*self.gicr.add(frame) };
+            let typer_low = gicr.rd.typer[0].read();
+
+            if gicr.rd.typer[1].read() == wanted {
+                return Some(gicr);
+            }
+            if typer_low & GICR_TYPER_LAST != 0 {
+                break;
+            }
         }
+        None
+    }
 
-        unsafe {
-            // We map your 'filter' constants to the ICC_SGI1R IRM (Interrupt Routing Mode) bit
-            // GICv3 IRM: 0 = Use Target List, 1 = All except self
-            let irm = match filter {
-                SGI_TARGET_ALL_EXCEPT_SELF => 1u32 << 24,
-                _ => 0u32,
+    /// Redistributor of the calling core
+    ///
+    /// The frame found by the first lookup (normally in `init_cpu`) is cached
+    /// per core and confirmed with a single GICR_TYPER read; the frames are
+    /// only walked again if it no longer matches.
+    ///
+    /// # Returns
+    /// The redistributor, or `GicError::NoRedistributor` if no frame matches
+    /// the calling core's MPIDR
+    pub fn redistributor(&self) -> Result<&GicrRegisters, GicError> {
+        let affinity = Affinity::current();
+        let slot = REDISTRIBUTOR_FRAMES.get(affinity.aff0 as usize);
+
+        if let Some(slot) = slot {
+            let cached = slot.load(Ordering::Relaxed);
+            let first = self.gicr as usize;
+            let end = first + GICR_MAX_FRAMES * GICR_FRAME_STRIDE;
+            if (first..end).contains(&cached) {
+                let gicr = unsafe { &*(cached as *const GicrRegisters) };
+                if gicr.rd.typer[1].read() == affinity.packed() {
+                    return Ok(gicr);
+                }
+            }
+        }
+
+        let gicr = self
+            .find_redistributor(affinity)
+            .ok_or(GicError::NoRedistributor(affinity.packed()))?;
+        if let Some(slot) = slot {
+            slot.store(gicr as *const GicrRegisters as usize, Ordering::Relaxed);
+        }
+        Ok(gicr)
+    }
+
+    /// Check that `intid` is implemented by this GIC and return its raw value
+    fn check_implemented(&self, intid: IntId) -> Result<u32, GicError> {
+        let id = intid.value();
+        if id < self.get_num_interrupts() {
+            Ok(id)
+        } else {
+            Err(GicError::NotImplemented(id))
+        }
+    }
+
+    /// Locate the register and bit for `intid` in a one-bit-per-interrupt bank
+    ///
+    /// SGIs and PPIs (0-31) are banked in the redistributor SGI frame, SPIs live
+    /// in the distributor bank `intid / 32`.
+    fn bit_register(&self, bank: BitBank, intid: u32) -> Result<(&Register<u32>, u32), GicError> {
+        let mask = 1u32 << (intid % 32);
+        if intid < SPI_BASE_ID {
+            let sgi = &self.redistributor()?.sgi;
+            let reg = match bank {
+                BitBank::Group => &sgi.igroupr0,
+                BitBank::SetEnable => &sgi.isenabler0,
+                BitBank::ClearEnable => &sgi.icenabler0,
+                BitBank::SetPending => &sgi.ispendr0,
+                BitBank::ClearPending => &sgi.icpendr0,
+                BitBank::SetActive => &sgi.isactiver0,
+                BitBank::ClearActive => &sgi.icactiver0,
             };
+            Ok((reg, mask))
+        } else {
+            let gicd = self.distributor();
+            let n = (intid / 32) as usize;
+            let reg = match bank {
+                BitBank::Group => &gicd.igroupr[n],
+                BitBank::SetEnable => &gicd.isenabler[n],
+                BitBank::ClearEnable => &gicd.icenabler[n],
+                BitBank::SetPending => &gicd.ispendr[n],
+                BitBank::ClearPending => &gicd.icpendr[n],
+                BitBank::SetActive => &gicd.isactiver[n],
+                BitBank::ClearActive => &gicd.icactiver[n],
+            };
+            Ok((reg, mask))
+        }
+    }
 
-            // ICC_SGI1R System Register Format (AArch32):
-            // Rt (Lower 32 bits): [31:24] SGI ID, [23:16] TargetList, [24] IRM
-            // Rt2 (Upper 32 bits): Affinity levels (0 for simple single-cluster setups)
+    /// Set the bit for `intid` in a write-1-to-act bank (ISENABLER, ICPENDR, ...)
+    fn write_bit(&self, bank: BitBank, intid: IntId) -> Result<(), GicError> {
+        let id = self.check_implemented(intid)?;
+        let (reg, mask) = self.bit_register(bank, id)?;
+        reg.write(mask);
+        Ok(())
+    }
 
-            let val: u32 = irm | ((target_list as u32) << 0) | ((sgi_id as u32) << 24);
+    /// Read the bit for `intid` in a one-bit-per-interrupt bank
+    fn read_bit(&self, bank: BitBank, intid: IntId) -> Result<bool, GicError> {
+        let id = self.check_implemented(intid)?;
+        let (reg, mask) = self.bit_register(bank, id)?;
+        Ok(reg.read() & mask != 0)
+    }
 
-            // CP15, 0, Rt, Rt2, c12 (ICC_SGI1R)
-            asm!(
-                "mcrr p15, 0, {}, {}, c12",
-                in(reg) val,
-                in(reg) 0u32 // Affinity 0.0.0
-            );
-            asm!("isb");
+    /// Locate the priority byte for `intid` (IPRIORITYR is byte-accessible)
+    fn priority_register(&self, intid: u32) -> Result<&Register<u8>, GicError> {
+        if intid < SPI_BASE_ID {
+            Ok(&self.redistributor()?.sgi.ipriorityr[intid as usize])
+        } else {
+            Ok(&self.distributor().ipriorityr[intid as usize])
         }
     }
 
-    /// Send SGI to a specific CPU
+    /// Wait until a distributor register write has taken effect (GICD_CTLR.RWP)
+    fn wait_for_distributor_rwp(&self) {
+        while (self.distributor().ctlr.read() & GICD_CTLR_RWP) != 0 {}
+    }
+
+    /// Wait until a redistributor register write has taken effect (GICR_CTLR.RWP)
+    fn wait_for_redistributor_rwp(&self) -> Result<(), GicError> {
+        let gicr = self.redistributor()?;
+        while (gicr.rd.ctlr.read() & GICR_CTLR_RWP) != 0 {}
+        Ok(())
+    }
+
+    /// Enable forwarding of an interrupt (SGI, PPI or SPI)
     ///
     /// # Arguments
-    /// * `sgi_id` - SGI number (0-15)
-    /// * `cpu_id` - Target CPU ID (0-15)
-    pub fn send_sgi_to_cpu(sgi_id: u8, cpu_id: u8) {
-        if cpu_id > 15 {
-            return; // Only support 16 CPUs max
+    /// * `intid` - Interrupt ID
+    pub fn enable_interrupt(&self, intid: impl Into<IntId>) -> Result<(), GicError> {
+        self.write_bit(BitBank::SetEnable, intid.into())
+    }
+
+    /// Disable forwarding of an interrupt (SGI, PPI or SPI)
+    ///
+    /// Waits for the write to take effect, so the interrupt is guaranteed
+    /// not to be signalled once this returns.
+    ///
+    /// # Arguments
+    /// * `intid` - Interrupt ID
+    pub fn disable_interrupt(&self, intid: impl Into<IntId>) -> Result<(), GicError> {
+        let intid = intid.into();
+        self.write_bit(BitBank::ClearEnable, intid)?;
+
+        if intid.is_spi() {
+            self.wait_for_distributor_rwp();
+        } else {
+            self.wait_for_redistributor_rwp()?;
         }
+        Ok(())
+    }
 
-        let target_mask = 1u16 << cpu_id;
-        Self::send_sgi(sgi_id, target_mask, SGI_TARGET_LIST);
+    /// Check if an interrupt is enabled
+    ///
+    /// # Arguments
+    /// * `intid` - Interrupt ID
+    pub fn is_interrupt_enabled(&self, intid: impl Into<IntId>) -> Result<bool, GicError> {
+        self.read_bit(BitBank::SetEnable, intid.into())
     }
 
-    /// Send SGI to all CPUs except the current one
+    /// Set the priority of an interrupt
     ///
     /// # Arguments
-    /// * `sgi_id` - SGI number (0-15)
-    pub fn send_sgi_to_all_except_self(sgi_id: u8) {
-        Self::send_sgi(sgi_id, 0, SGI_TARGET_ALL_EXCEPT_SELF);
+    /// * `intid` - Interrupt ID
+    /// * `priority` - Priority value (0-255, lower value = higher priority)
+    pub fn set_interrupt_priority(
+        &self,
+        intid: impl Into<IntId>,
+        priority: u8,
+    ) -> Result<(), GicError> {
+        let id = self.check_implemented(intid.into())?;
+        self.priority_register(id)?.write(priority);
+        Ok(())
     }
 
-    /// Send SGI to self
+    /// Read the priority of an interrupt
     ///
     /// # Arguments
-    /// * `sgi_id` - SGI number (0-15)
+    /// * `intid` - Interrupt ID
+    pub fn get_interrupt_priority(&self, intid: impl Into<IntId>) -> Result<u8, GicError> {
+        let id = self.check_implemented(intid.into())?;
+        Ok(self.priority_register(id)?.read())
+    }
 
-    pub fn send_sgi_to_self(sgi_id: u8) {
-        if sgi_id > 15 {
-            return;
+    /// Assign an interrupt to Group 0 or Group 1
+    ///
+    /// # Arguments
+    /// * `intid` - Interrupt ID
+    /// * `group` - Target interrupt group
+    pub fn set_interrupt_group(
+        &self,
+        intid: impl Into<IntId>,
+        group: InterruptGroup,
+    ) -> Result<(), GicError> {
+        let id = self.check_implemented(intid.into())?;
+        let (reg, mask) = self.bit_register(BitBank::Group, id)?;
+        reg.modify(|current| match group {
+            InterruptGroup::Group0 => current & !mask,
+            InterruptGroup::Group1 => current | mask,
+        });
+        Ok(())
+    }
+
+    /// Read the group an interrupt is assigned to
+    ///
+    /// # Arguments
+    /// * `intid` - Interrupt ID
+    pub fn get_interrupt_group(&self, intid: impl Into<IntId>) -> Result<InterruptGroup, GicError> {
+        if self.read_bit(BitBank::Group, intid.into())? {
+            Ok(InterruptGroup::Group1)
+        } else {
+            Ok(InterruptGroup::Group0)
         }
+    }
 
-        unsafe {
-            // ICC_SGI1R (System Generated Interrupt Group 1 Register)
-            // Format for AArch32 (Cortex-R52):
-            // Rt = SGI ID and other controls, Rt2 = Affinity bits
+    /// Set an interrupt pending (software trigger of a PPI or SPI)
+    ///
+    /// # Arguments
+    /// * `intid` - Interrupt ID
+    pub fn set_interrupt_pending(&self, intid: impl Into<IntId>) -> Result<(), GicError> {
+        self.write_bit(BitBank::SetPending, intid.into())
+    }
 
-            let sgi_id_u32 = sgi_id as u32;
-            let irm = 1u32 << 24; // Interrupt Routing Mode: 1 = "All except self"
-            // To send to SELF specifically, we use IRM = 0 and set the target list
+    /// Clear the pending state of an interrupt
+    ///
+    /// # Arguments
+    /// * `intid` - Interrupt ID
+    pub fn clear_interrupt_pending(&self, intid: impl Into<IntId>) -> Result<(), GicError> {
+        self.write_bit(BitBank::ClearPending, intid.into())
+    }
 
-            // Target list for CPU 0 in the current cluster is bit 0
-            let target_list = 0b1u32;
-            let val: u32 = (target_list << 0) | (sgi_id_u32 << 24);
+    /// Check if an interrupt is pending
+    ///
+    /// # Arguments
+    /// * `intid` - Interrupt ID
+    pub fn is_interrupt_pending(&self, intid: impl Into<IntId>) -> Result<bool, GicError> {
+        self.read_bit(BitBank::SetPending, intid.into())
+    }
 
-            // ICC_SGI1R is a 64-bit register accessed via MCRR
-            // CP15, 0, Rt, Rt2, c12
-            asm!(
-                "mcrr p15, 0, {}, {}, c12",
-                in(reg) val,    // Lower 32 bits (Target List, etc)
-                in(reg) 0u32    // Upper 32 bits (Affinity levels)
-            );
-            asm!("isb");
+    /// Locate the ICFGR register and edge bit for `intid`
+    ///
+    /// Each ICFGR holds 16 interrupts, 2 bits each; bit [2n+1] selects edge.
+    /// SGIs and PPIs use GICR_ICFGR0/1, SPIs use GICD_ICFGRn.
+    fn trigger_register(&self, intid: u32) -> Result<(&Register<u32>, u32), GicError> {
+        let edge_bit = 1u32 << ((intid % 16) * 2 + 1);
+        let n = (intid / 16) as usize;
+        if intid < SPI_BASE_ID {
+            Ok((&self.redistributor()?.sgi.icfgr[n], edge_bit))
+        } else {
+            Ok((&self.distributor().icfgr[n], edge_bit))
         }
     }
 
-    /// Set the priority of an SGI
+    /// Set an interrupt active (e.g. to restore state saved across power-down)
     ///
     /// # Arguments
-    /// * `sgi_id` - SGI number (0-15)
-    /// * `priority` - Priority value (0-255, lower value = higher priority)
-    pub fn set_sgi_priority(sgi_id: u8, priority: u8) {
-        if sgi_id > 15 {
-            return;
-        }
+    /// * `intid` - Interrupt ID
+    pub fn set_interrupt_active(&self, intid: impl Into<IntId>) -> Result<(), GicError> {
+        self.write_bit(BitBank::SetActive, intid.into())
+    }
 
-        unsafe {
-            // Each priority register holds 4 priorities
-            let reg_index = (sgi_id / 4) as usize;
-            let byte_offset = (sgi_id % 4) * 8;
+    /// Clear the active state of an interrupt
+    ///
+    /// Unlike ICC_DIR this works for any interrupt, including ones acknowledged
+    /// by another core, and does not affect the running priority.
+    ///
+    /// # Arguments
+    /// * `intid` - Interrupt ID
+    pub fn clear_interrupt_active(&self, intid: impl Into<IntId>) -> Result<(), GicError> {
+        self.write_bit(BitBank::ClearActive, intid.into())
+    }
 
-            let priority_reg = GICR_IPRIORITYR.add(reg_index);
-            let mut current = read_volatile(priority_reg);
+    /// Check if an interrupt is active
+    ///
+    /// # Arguments
+    /// * `intid` - Interrupt ID
+    pub fn is_interrupt_active(&self, intid: impl Into<IntId>) -> Result<bool, GicError> {
+        self.read_bit(BitBank::SetActive, intid.into())
+    }
 
-            // Clear the old priority and set new one
-            let mask = 0xFF << byte_offset;
-            current = (current & !mask) | ((priority as u32) << byte_offset);
+    /// Configure a PPI or SPI as edge-triggered or level-sensitive
+    ///
+    /// SGIs are always edge-triggered; requesting `TriggerMode::Level` for one
+    /// is rejected. The interrupt should be disabled while its trigger mode is
+    /// changed.
+    ///
+    /// # Arguments
+    /// * `intid` - Interrupt ID
+    /// * `mode` - Trigger mode
+    pub fn set_trigger_mode(
+        &self,
+        intid: impl Into<IntId>,
+        mode: TriggerMode,
+    ) -> Result<(), GicError> {
+        let intid = intid.into();
+        let id = self.check_implemented(intid)?;
+        if intid.is_sgi() {
+            // GICR_ICFGR0 is read-only: SGIs are fixed edge-triggered
+            return match mode {
+                TriggerMode::Edge => Ok(()),
+                TriggerMode::Level => Err(GicError::SgiTriggerFixed(id)),
+            };
+        }
 
-            write_volatile(priority_reg, current);
+        let (reg, edge_bit) = self.trigger_register(id)?;
+        reg.modify(|current| match mode {
+            TriggerMode::Level => current & !edge_bit,
+            TriggerMode::Edge => current | edge_bit,
+        });
+
+        // Some implementations fix the trigger of a line; report if it did not stick
+        if self.get_trigger_mode(intid)? == mode {
+            Ok(())
+        } else {
+            Err(GicError::TriggerNotConfigurable(id))
         }
     }
 
-    /// Read the GIC Distributor Type Register
-    /// Returns information about the GIC configuration
-    pub fn read_typer() -> u32 {
-        unsafe { read_volatile(GICD_TYPER) }
+    /// Read the trigger mode of an interrupt
+    ///
+    /// # Arguments
+    /// * `intid` - Interrupt ID
+    pub fn get_trigger_mode(&self, intid: impl Into<IntId>) -> Result<TriggerMode, GicError> {
+        let intid = intid.into();
+        let id = self.check_implemented(intid)?;
+        if intid.is_sgi() {
+            return Ok(TriggerMode::Edge);
+        }
+
+        let (reg, edge_bit) = self.trigger_register(id)?;
+        if reg.read() & edge_bit != 0 {
+            Ok(TriggerMode::Edge)
+        } else {
+            Ok(TriggerMode::Level)
+        }
     }
 
-    /// Get the number of implemented interrupt lines
-    pub fn get_num_interrupts() -> u32 {
-        let typer = Self::read_typer();
-        // ITLinesNumber field [4:0] indicates (N+1)*32 interrupts
-        let it_lines = typer & 0x1F;
-        (it_lines + 1) * 32
+    /// Route an SPI to a specific core or to any participating core
+    ///
+    /// # Arguments
+    /// * `spi` - SPI interrupt ID
+    /// * `route` - Routing target
+    pub fn set_spi_route(&self, spi: SpiId, route: SpiRoute) -> Result<(), GicError> {
+        let id = self.check_implemented(spi.into())?;
+
+        // IROUTER layout: [39:32] Aff3, [31] IRM, [23:16] Aff2, [15:8] Aff1, [7:0] Aff0
+        let (low, high) = match route {
+            SpiRoute::Core(aff) => (
+                (aff.aff2 as u32) << 16 | (aff.aff1 as u32) << 8 | aff.aff0 as u32,
+                aff.aff3 as u32,
+            ),
+            SpiRoute::AnyParticipating => (GICD_IROUTER_IRM, 0),
+        };
+
+        let router = &self.distributor().irouter[id as usize];
+        router[0].write(low);
+        router[1].write(high);
+        Ok(())
+    }
+
+    /// Route an SPI to a core in the calling core's cluster
+    ///
+    /// # Arguments
+    /// * `spi` - SPI interrupt ID
+    /// * `core` - Core number within the cluster (MPIDR Aff0)
+    pub fn route_spi_to_core(&self, spi: SpiId, core: u8) -> Result<(), GicError> {
+        let aff = Affinity {
+            aff0: core,
+            ..Affinity::current()
+        };
+        self.set_spi_route(spi, SpiRoute::Core(aff))
+    }
+
+    /// Read the current routing of an SPI
+    ///
+    /// # Arguments
+    /// * `spi` - SPI interrupt ID
+    pub fn get_spi_route(&self, spi: SpiId) -> Result<SpiRoute, GicError> {
+        let id = self.check_implemented(spi.into())?;
+
+        let router = &self.distributor().irouter[id as usize];
+        let (low, high) = (router[0].read(), router[1].read());
+
+        if low & GICD_IROUTER_IRM != 0 {
+            Ok(SpiRoute::AnyParticipating)
+        } else {
+            Ok(SpiRoute::Core(Affinity::new(
+                high as u8,
+                (low >> 16) as u8,
+                (low >> 8) as u8,
+                low as u8,
+            )))
+        }
     }
 
     /// Check if a specific SGI is enabled
     ///
     /// # Arguments
-    /// * `sgi_id` - SGI number (0-15)
+    /// * `sgi_id` - SGI number
     ///
     /// # Returns
     /// `true` if the SGI is enabled, `false` otherwise
-    pub fn is_sgi_enabled(sgi_id: u8) -> bool {
-        if sgi_id > 15 {
-            return false;
-        }
+    pub fn is_sgi_enabled(&self, sgi_id: SgiId) -> Result<bool, GicError> {
+        let enabled = self.redistributor()?.sgi.isenabler0.read();
+        Ok((enabled & (1 << sgi_id.value())) != 0)
+    }
 
-        unsafe {
-            let enabled = read_volatile(GICR_ISENABLER0);
-            (enabled & (1 << sgi_id)) != 0
+    /// Print a human-readable report of the GIC state over the UART
+    ///
+    /// Lists the distributor and CPU interface registers, followed by one line
+    /// per implemented interrupt ID with its enable, pending, active, group,
+    /// priority, trigger and (for SPIs) routing state. SGI and PPI state is
+    /// that of the calling core.
+    pub fn dump_state(&self) {
+        let mut uart = Uart;
+        let num_interrupts = self.get_num_interrupts().min(SPECIAL_INTERRUPT_ID_START);
+        let affinity = Affinity::current();
+
+        let _ = writeln!(uart, "==== GIC state ====");
+        let _ = writeln!(
+            uart,
+            "core {}  GICD @ {:#010x}  GICR @ {:#010x}",
+            affinity,
+            self.config().gicd_base,
+            self.find_redistributor(affinity)
+                .map_or(0, |gicr| gicr as *const GicrRegisters as usize)
+        );
+        let _ = writeln!(
+            uart,
+            "GICD_CTLR={:#010x} GICD_TYPER={:#010x} ({} INTIDs)",
+            self.distributor().ctlr.read(),
+            self.read_typer(),
+            num_interrupts
+        );
+        let _ = writeln!(
+            uart,
+            "ICC_CTLR={:#010x} ICC_SRE={:#x} ICC_IGRPEN0={} ICC_IGRPEN1={}",
+            Self::read_icc_ctlr(),
+            Self::read_icc_sre(),
+            Self::read_icc_igrpen0(),
+            Self::read_icc_igrpen1()
+        );
+        let _ = writeln!(
+            uart,
+            "ICC_PMR={:#04x} ICC_BPR0={} ICC_BPR1={} ICC_RPR={:#04x} ICC_HPPIR0={} ICC_HPPIR1={}",
+            Self::read_priority_mask(),
+            Self::read_binary_point_group0(),
+            Self::read_binary_point_group1(),
+            Self::read_running_priority(),
+            Self::get_interrupt_id(Self::read_highest_pending_group0()),
+            Self::get_interrupt_id(Self::read_highest_pending())
+        );
+
+        let _ = writeln!(uart, "INTID EN PEND ACT GRP PRIO TRIG  ROUTE");
+        for id in 0..num_interrupts {
+            // Every ID below num_interrupts is implemented, so the queries cannot fail
+            let Ok(intid) = IntId::new(id) else { break };
+            let _ = write!(
+                uart,
+                "{:5} {:2} {:4} {:3} {:3} {:#04x} {:5} ",
+                id,
+                self.is_interrupt_enabled(intid).unwrap_or(false) as u8,
+                self.is_interrupt_pending(intid).unwrap_or(false) as u8,
+                self.is_interrupt_active(intid).unwrap_or(false) as u8,
+                match self.get_interrupt_group(intid) {
+                    Ok(InterruptGroup::Group0) => "0",
+                    Ok(InterruptGroup::Group1) => "1",
+                    Err(_) => "?",
+                },
+                self.get_interrupt_priority(intid).unwrap_or(0),
+                match self.get_trigger_mode(intid) {
+                    Ok(TriggerMode::Level) => "level",
+                    Ok(TriggerMode::Edge) => "edge",
+                    Err(_) => "?",
+                }
+            );
+            let _ = match SpiId::new(id).and_then(|spi| self.get_spi_route(spi)) {
+                Ok(SpiRoute::Core(aff)) => writeln!(uart, "{}", aff),
+                Ok(SpiRoute::AnyParticipating) => writeln!(uart, "any"),
+                Err(_) => writeln!(uart, "local"),
+            };
         }
     }
 
@@ -535,26 +1739,41 @@ impl Gic {
 
 // ==================== Convenience Functions ====================
 
-/// Initialize the GIC (convenience function)
+/// Initialize the GIC at the Arm FVP BaseR addresses (convenience function)
+///
+/// Use [`gic_init_from_cbar`] or [`Gic::new`] on other platforms. A core with
+/// no redistributor frame is left with its CPU interface disabled.
 #[inline]
 pub fn gic_init() {
-    Gic::init();
+    let gic = unsafe { Gic::new(GicConfig::FVP_BASER) };
+    let _ = gic.init();
+}
+
+/// Initialize the Cortex-R52 internal GIC found through CBAR (convenience function)
+///
+/// Returns the driver instance for further configuration, or
+/// `GicError::NoRedistributor` if this core has no redistributor frame.
+#[inline]
+pub fn gic_init_from_cbar() -> Result<Gic, GicError> {
+    let gic = Gic::from_cbar();
+    gic.init()?;
+    Ok(gic)
 }
 
 /// Send an SGI to a specific CPU (convenience function)
 #[inline]
-pub fn send_sgi(sgi_id: u8, cpu_id: u8) {
+pub fn send_sgi(sgi_id: SgiId, cpu_id: u8) {
     Gic::send_sgi_to_cpu(sgi_id, cpu_id);
 }
 
 /// Send an SGI to all CPUs except self (convenience function)
 #[inline]
-pub fn send_sgi_broadcast(sgi_id: u8) {
+pub fn send_sgi_broadcast(sgi_id: SgiId) {
     Gic::send_sgi_to_all_except_self(sgi_id);
 }
 
 /// Send an SGI to self (convenience function)
 #[inline]
-pub fn send_sgi_to_self(sgi_id: u8) {
+pub fn send_sgi_to_self(sgi_id: SgiId) {
     Gic::send_sgi_to_self(sgi_id);
 }
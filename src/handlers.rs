@@ -1,44 +1,67 @@
+#[cfg(feature = "default-handlers")]
+use core::fmt::Write;
+
+#[cfg(feature = "default-handlers")]
+use crate::exception::{ExceptionFrame, Fault, HypTrapFrame};
+#[cfg(feature = "default-handlers")]
+use crate::uart::Uart;
 use crate::uart::print_uart;
 
 // Default handler implementations are only compiled if the "default-handlers" feature is enabled
 // This allows applications to provide their own handlers without conflicts
 
-#[cfg(feature = "default-handlers")]
+// With "irq-dispatcher" enabled, rust_irq_handler is provided by the dispatcher instead
+#[cfg(all(feature = "default-handlers", not(feature = "irq-dispatcher")))]
 #[unsafe(no_mangle)]
 pub extern "C" fn rust_irq_handler() {
     print_uart("Interrupt Received!\n");
 }
 
-#[cfg(feature = "default-handlers")]
+// With "svc-dispatcher" enabled, rust_swi_handler is provided by the syscall module instead
+#[cfg(all(feature = "default-handlers", not(feature = "svc-dispatcher")))]
 #[unsafe(no_mangle)]
-pub extern "C" fn rust_swi_handler() {
+pub extern "C" fn rust_swi_handler(_frame: &mut ExceptionFrame) {
     print_uart("Software Interrupt (SWI) Called\n");
 }
 
-#[cfg(feature = "default-handlers")]
+// With "irq-dispatcher" enabled, rust_fiq_handler is provided by the dispatcher instead
+#[cfg(all(feature = "default-handlers", not(feature = "irq-dispatcher")))]
 #[unsafe(no_mangle)]
-pub extern "C" fn fiq_handler_asm() {
+pub extern "C" fn rust_fiq_handler() {
     print_uart("FIQ Handler Called\n");
     loop {}
 }
 
 #[cfg(feature = "default-handlers")]
 #[unsafe(no_mangle)]
-pub extern "C" fn rust_undef_handler() {
+pub extern "C" fn rust_undef_handler(frame: &mut ExceptionFrame) {
     print_uart("Undefined Instruction Exception\n");
+    let _ = write!(Uart, "at {:#010x}\n{}", frame.lr, frame);
     loop {}
 }
 
 #[cfg(feature = "default-handlers")]
 #[unsafe(no_mangle)]
-pub extern "C" fn rust_data_abort_handler() {
+pub extern "C" fn rust_data_abort_handler(frame: &mut ExceptionFrame) {
     print_uart("Data Abort Exception\n");
+    let _ = write!(Uart, "{}\n{}", Fault::data_abort(), frame);
     loop {}
 }
 
 #[cfg(feature = "default-handlers")]
 #[unsafe(no_mangle)]
-pub extern "C" fn rust_prefetch_abort_handler() {
+pub extern "C" fn rust_prefetch_abort_handler(frame: &mut ExceptionFrame) {
     print_uart("Prefetch Abort Exception\n");
+    let _ = write!(Uart, "{}\n{}", Fault::prefetch_abort(), frame);
     loop {}
 }
+
+#[cfg(feature = "default-handlers")]
+#[unsafe(no_mangle)]
+pub extern "C" fn rust_hyp_trap_handler(frame: &mut HypTrapFrame) {
+    print_uart("Hyp Mode Exception\n");
+    let _ = write!(Uart, "{}", frame);
+    loop {
+        core::hint::spin_loop();
+    }
+}
@@ -1,5 +1,8 @@
 use core::arch::asm;
 
+use crate::gic::Gic;
+use crate::system::read_cpsr;
+
 pub fn enable_irq() {
     unsafe {
         asm!("cpsie i");
@@ -28,3 +31,203 @@ pub fn disable_fiq() {
         asm!("cpsid f");
     }
 }
+
+/// CPSR I bit: IRQs masked
+pub const CPSR_I_BIT: u32 = 0x80;
+
+/// CPSR F bit: FIQs masked
+pub const CPSR_F_BIT: u32 = 0x40;
+
+/// CPSR mode field [4:0]
+const CPSR_MODE_MASK: u32 = 0x1F;
+
+/// CPU mode definitions (CPSR.M)
+pub(crate) const ARM_MODE_USR: u32 = 0x10;
+pub(crate) const ARM_MODE_FIQ: u32 = 0x11;
+pub(crate) const ARM_MODE_IRQ: u32 = 0x12;
+pub(crate) const ARM_MODE_SVC: u32 = 0x13;
+pub(crate) const ARM_MODE_ABT: u32 = 0x17;
+pub(crate) const ARM_MODE_HYP: u32 = 0x1A;
+pub(crate) const ARM_MODE_UND: u32 = 0x1B;
+pub(crate) const ARM_MODE_SYS: u32 = 0x1F;
+
+/// Processor mode, as encoded in CPSR.M
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessorMode {
+    User,
+    Fiq,
+    Irq,
+    Supervisor,
+    Abort,
+    Hyp,
+    Undefined,
+    System,
+    /// Reserved encoding
+    Unknown(u8),
+}
+
+impl ProcessorMode {
+    /// Decode a CPSR/SPSR mode field
+    pub const fn from_bits(psr: u32) -> Self {
+        match psr & CPSR_MODE_MASK {
+            ARM_MODE_USR => ProcessorMode::User,
+            ARM_MODE_FIQ => ProcessorMode::Fiq,
+            ARM_MODE_IRQ => ProcessorMode::Irq,
+            ARM_MODE_SVC => ProcessorMode::Supervisor,
+            ARM_MODE_ABT => ProcessorMode::Abort,
+            ARM_MODE_HYP => ProcessorMode::Hyp,
+            ARM_MODE_UND => ProcessorMode::Undefined,
+            ARM_MODE_SYS => ProcessorMode::System,
+            other => ProcessorMode::Unknown(other as u8),
+        }
+    }
+}
+
+/// Mode the calling code is running in
+pub fn current_mode() -> ProcessorMode {
+    ProcessorMode::from_bits(read_cpsr())
+}
+
+/// Check whether IRQs are unmasked (CPSR.I clear)
+pub fn irqs_enabled() -> bool {
+    (read_cpsr() & CPSR_I_BIT) == 0
+}
+
+/// Check whether FIQs are unmasked (CPSR.F clear)
+pub fn fiqs_enabled() -> bool {
+    (read_cpsr() & CPSR_F_BIT) == 0
+}
+
+/// Interrupt mask bits (CPSR.I and CPSR.F) saved before masking
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InterruptState(u32);
+
+impl InterruptState {
+    /// Whether IRQs were unmasked when the state was saved
+    pub const fn irq_enabled(self) -> bool {
+        (self.0 & CPSR_I_BIT) == 0
+    }
+
+    /// Whether FIQs were unmasked when the state was saved
+    pub const fn fiq_enabled(self) -> bool {
+        (self.0 & CPSR_F_BIT) == 0
+    }
+}
+
+/// Mask IRQs and return the previous mask state
+pub fn save_and_disable_irq() -> InterruptState {
+    let state = InterruptState(read_cpsr() & (CPSR_I_BIT | CPSR_F_BIT));
+    disable_irq();
+    state
+}
+
+/// Mask IRQs and FIQs and return the previous mask state
+pub fn save_and_disable_interrupts() -> InterruptState {
+    let state = InterruptState(read_cpsr() & (CPSR_I_BIT | CPSR_F_BIT));
+    unsafe {
+        asm!("cpsid if");
+    }
+    state
+}
+
+/// Restore the IRQ and FIQ masks saved by `save_and_disable_irq` or
+/// `save_and_disable_interrupts`
+///
+/// Only unmasks what was unmasked when `state` was saved; other bits of the
+/// CPSR are left untouched.
+///
+/// # Safety
+/// Unmasking can end a critical section the caller is still relying on; states
+/// must be restored in the reverse order they were saved.
+pub unsafe fn restore_interrupts(state: InterruptState) {
+    if state.fiq_enabled() {
+        enable_fiq();
+    }
+    if state.irq_enabled() {
+        enable_irq();
+    }
+}
+
+/// Masks interrupts on the calling core until dropped, then restores the
+/// previous CPSR.I/F state
+pub struct InterruptGuard {
+    state: InterruptState,
+}
+
+impl InterruptGuard {
+    /// Mask IRQs only
+    pub fn irq() -> Self {
+        Self {
+            state: save_and_disable_irq(),
+        }
+    }
+
+    /// Mask IRQs and FIQs
+    pub fn all() -> Self {
+        Self {
+            state: save_and_disable_interrupts(),
+        }
+    }
+}
+
+impl Drop for InterruptGuard {
+    fn drop(&mut self) {
+        unsafe { restore_interrupts(self.state) };
+    }
+}
+
+/// Run `f` with IRQs and FIQs masked, restoring the previous state afterwards
+pub fn free<R>(f: impl FnOnce() -> R) -> R {
+    let _guard = InterruptGuard::all();
+    f()
+}
+
+/// Run `f` with IRQs masked, restoring the previous state afterwards
+pub fn free_irq<R>(f: impl FnOnce() -> R) -> R {
+    let _guard = InterruptGuard::irq();
+    f()
+}
+
+/// Critical section that masks interrupts by priority instead of globally
+///
+/// While the guard is alive, ICC_PMR is at most `threshold`: interrupts with a
+/// priority value numerically at or above `threshold` stay pending, while more
+/// urgent ones (lower values) keep preempting. The previous mask is restored
+/// when the guard is dropped, so sections nest; an inner section can only mask
+/// more, never less, than the one enclosing it.
+///
+/// The Cortex-R52 implements 5 priority bits, so only the top 5 bits of
+/// `threshold` are significant.
+pub struct PriorityMaskGuard {
+    previous: u32,
+}
+
+impl PriorityMaskGuard {
+    /// Raise the priority mask to `threshold` until the guard is dropped
+    ///
+    /// # Arguments
+    /// * `threshold` - Lowest priority value (most urgent) that is masked
+    pub fn new(threshold: u8) -> Self {
+        let previous = Gic::read_priority_mask();
+        if (threshold as u32) < previous {
+            Gic::write_priority_mask(threshold as u32);
+        }
+        Self { previous }
+    }
+}
+
+impl Drop for PriorityMaskGuard {
+    fn drop(&mut self) {
+        Gic::write_priority_mask(self.previous);
+    }
+}
+
+/// Run `f` with interrupts of priority value `threshold` and above masked
+///
+/// # Arguments
+/// * `threshold` - Lowest priority value (most urgent) that is masked
+/// * `f` - Code to run inside the critical section
+pub fn with_priority_mask<R>(threshold: u8, f: impl FnOnce() -> R) -> R {
+    let _guard = PriorityMaskGuard::new(threshold);
+    f()
+}
@@ -1,18 +1,36 @@
 #![no_std]
 
 pub mod arm_generic_timer;
+mod boot;
+#[cfg(any(
+    feature = "critical-section-single-core",
+    feature = "critical-section-multi-core"
+))]
+mod critical_section_impl;
+pub mod dispatcher;
+pub mod exception;
 pub mod gic;
 pub mod handlers;
 pub mod irq;
+pub mod mmio;
+pub mod mpu;
+pub mod syscall;
 pub mod system;
+pub mod tcm;
 pub mod uart;
 
 // Re-export commonly used items
 pub use arm_generic_timer::*;
+pub use dispatcher::*;
+pub use exception::*;
 pub use gic::*;
 pub use handlers::*;
 pub use irq::*;
+pub use mmio::*;
+pub use mpu::*;
+pub use syscall::*;
 pub use system::*;
+pub use tcm::*;
 pub use uart::*;
 
 #[cfg(feature = "panic-handler")]
@@ -0,0 +1,32 @@
+/// Memory-mapped register access
+///
+/// Peripheral register blocks are described as `#[repr(C)]` structs of
+/// [`Register`] fields laid out at their hardware offsets. A reference to such
+/// a struct is obtained from the peripheral's base address, and every field
+/// access is a single volatile load or store of the register's width.
+use core::cell::UnsafeCell;
+use core::ptr::{read_volatile, write_volatile};
+
+/// A memory-mapped register of width `T` (`u8`, `u32`, ...)
+#[repr(transparent)]
+pub struct Register<T: Copy>(UnsafeCell<T>);
+
+impl<T: Copy> Register<T> {
+    /// Read the register
+    #[inline(always)]
+    pub fn read(&self) -> T {
+        unsafe { read_volatile(self.0.get()) }
+    }
+
+    /// Write the register
+    #[inline(always)]
+    pub fn write(&self, value: T) {
+        unsafe { write_volatile(self.0.get(), value) }
+    }
+
+    /// Read the register, transform the value and write it back
+    #[inline(always)]
+    pub fn modify(&self, f: impl FnOnce(T) -> T) {
+        self.write(f(self.read()));
+    }
+}
@@ -0,0 +1,671 @@
+/// Memory Protection Units (PMSAv8-32)
+///
+/// [`Mpu`] is the EL1 MPU, programmed by the operating system at EL1.
+/// [`HypMpu`] is the EL2 MPU: it protects Hyp mode itself and, once enabled
+/// for EL1 (HCR.VM), confines everything running at EL1 and EL0 to its regions
+/// regardless of the EL1 MPU configuration. It is only accessible in Hyp mode,
+/// so it is set up from the `el2_init` hook that `_reset` calls before
+/// dropping to EL1.
+///
+/// Each MPU region is described by an [`MpuRegion`]: a 64-byte aligned base,
+/// an inclusive limit, access permissions, execute-never, shareability and an
+/// index into the memory attributes held in MAIR0/MAIR1.
+///
+/// PMSAv8 regions must not overlap: an access that hits more than one enabled
+/// region takes a permission fault. [`Mpu::configure`] checks the granularity
+/// and overlap rules for the whole table before any register is written.
+///
+/// # Example
+/// ```ignore
+/// unsafe {
+///     Mpu::set_mair([MAIR_NORMAL_WRITE_BACK, MAIR_DEVICE_NGNRE, 0, 0, 0, 0, 0, 0]);
+///     Mpu::configure(&REGIONS)?;
+///     Mpu::enable(false);
+/// }
+/// ```
+use core::arch::asm;
+
+/// MPU region granularity in bytes
+pub const MPU_GRANULE: u32 = 64;
+const MPU_GRANULE_MASK: u32 = MPU_GRANULE - 1;
+
+/// Number of attribute slots in MAIR0/MAIR1
+pub const MPU_NUM_ATTRS: u8 = 8;
+
+/// MAIR attribute: Device-nGnRnE (strongly ordered)
+pub const MAIR_DEVICE_NGNRNE: u8 = 0x00;
+/// MAIR attribute: Device-nGnRE (peripheral registers)
+pub const MAIR_DEVICE_NGNRE: u8 = 0x04;
+/// MAIR attribute: Normal, inner and outer non-cacheable
+pub const MAIR_NORMAL_NON_CACHEABLE: u8 = 0x44;
+/// MAIR attribute: Normal, inner and outer write-through, read/write-allocate
+pub const MAIR_NORMAL_WRITE_THROUGH: u8 = 0xBB;
+/// MAIR attribute: Normal, inner and outer write-back, read/write-allocate
+pub const MAIR_NORMAL_WRITE_BACK: u8 = 0xFF;
+
+/// SCTLR.M: MPU enable
+const SCTLR_M: u32 = 1 << 0;
+/// SCTLR.BR: background region enable for privileged accesses
+const SCTLR_BR: u32 = 1 << 17;
+
+/// PRBAR field layout
+const PRBAR_XN: u32 = 1 << 0;
+const PRBAR_AP_SHIFT: u32 = 1;
+const PRBAR_SH_SHIFT: u32 = 3;
+
+/// PRLAR field layout
+const PRLAR_EN: u32 = 1 << 0;
+const PRLAR_ATTRINDX_SHIFT: u32 = 1;
+
+/// Access permissions (PRBAR.AP)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessPermission {
+    /// Read/write at EL1, no access at EL0
+    PrivReadWrite,
+    /// Read/write at EL1 and EL0
+    ReadWrite,
+    /// Read-only at EL1, no access at EL0
+    PrivReadOnly,
+    /// Read-only at EL1 and EL0
+    ReadOnly,
+}
+
+impl AccessPermission {
+    const fn bits(self) -> u32 {
+        match self {
+            AccessPermission::PrivReadWrite => 0b00,
+            AccessPermission::ReadWrite => 0b01,
+            AccessPermission::PrivReadOnly => 0b10,
+            AccessPermission::ReadOnly => 0b11,
+        }
+    }
+
+    const fn from_bits(bits: u32) -> Self {
+        match bits & 0b11 {
+            0b00 => AccessPermission::PrivReadWrite,
+            0b01 => AccessPermission::ReadWrite,
+            0b10 => AccessPermission::PrivReadOnly,
+            _ => AccessPermission::ReadOnly,
+        }
+    }
+}
+
+/// Shareability domain (PRBAR.SH), only meaningful for Normal memory
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shareability {
+    NonShareable,
+    OuterShareable,
+    InnerShareable,
+}
+
+impl Shareability {
+    const fn bits(self) -> u32 {
+        match self {
+            Shareability::NonShareable => 0b00,
+            Shareability::OuterShareable => 0b10,
+            Shareability::InnerShareable => 0b11,
+        }
+    }
+
+    const fn from_bits(bits: u32) -> Self {
+        match bits & 0b11 {
+            0b10 => Shareability::OuterShareable,
+            0b11 => Shareability::InnerShareable,
+            // 0b01 is reserved and treated as non-shareable
+            _ => Shareability::NonShareable,
+        }
+    }
+}
+
+/// MPU driver errors
+///
+/// A `u8` payload is the region number the error refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MpuError {
+    /// Base address not aligned to 64 bytes
+    UnalignedBase(u8),
+    /// Limit does not end on a 64-byte boundary (limit + 1 not a multiple of 64)
+    UnalignedLimit(u8),
+    /// Limit below base
+    EmptyRegion(u8),
+    /// Attribute index outside 0-7
+    InvalidAttrIndex(u8),
+    /// The two regions cover common addresses
+    Overlap(u8, u8),
+    /// Region number beyond the regions implemented (MPUIR.DREGION)
+    NotImplemented(u8),
+    /// The region table is longer than the number of regions implemented
+    TooManyRegions { requested: usize, available: u8 },
+}
+
+/// MPU region descriptor
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MpuRegion {
+    /// First address of the region, a multiple of 64
+    pub base: u32,
+    /// Last address of the region (inclusive), one below a multiple of 64
+    pub limit: u32,
+    pub access: AccessPermission,
+    /// Instruction fetches from the region fault
+    pub execute_never: bool,
+    pub shareability: Shareability,
+    /// Attribute slot in MAIR0/MAIR1 (0-7)
+    pub attr_index: u8,
+}
+
+impl MpuRegion {
+    /// Check the alignment and attribute index rules for region number `number`
+    pub const fn validate(&self, number: u8) -> Result<(), MpuError> {
+        if self.base & MPU_GRANULE_MASK != 0 {
+            Err(MpuError::UnalignedBase(number))
+        } else if self.limit & MPU_GRANULE_MASK != MPU_GRANULE_MASK {
+            Err(MpuError::UnalignedLimit(number))
+        } else if self.limit < self.base {
+            Err(MpuError::EmptyRegion(number))
+        } else if self.attr_index >= MPU_NUM_ATTRS {
+            Err(MpuError::InvalidAttrIndex(number))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns true if the two regions cover any common address
+    pub const fn overlaps(&self, other: &MpuRegion) -> bool {
+        self.base <= other.limit && other.base <= self.limit
+    }
+
+    /// Encode the region's PRBAR/HPRBAR value
+    pub const fn prbar(&self) -> u32 {
+        let xn = if self.execute_never { PRBAR_XN } else { 0 };
+        (self.base & !MPU_GRANULE_MASK)
+            | (self.shareability.bits() << PRBAR_SH_SHIFT)
+            | (self.access.bits() << PRBAR_AP_SHIFT)
+            | xn
+    }
+
+    /// Encode the region's PRLAR/HPRLAR value, with the region enabled
+    pub const fn prlar(&self) -> u32 {
+        (self.limit & !MPU_GRANULE_MASK)
+            | (((self.attr_index & 0x7) as u32) << PRLAR_ATTRINDX_SHIFT)
+            | PRLAR_EN
+    }
+
+    /// Decode a PRBAR/PRLAR pair; `None` if the region is disabled
+    pub const fn from_registers(prbar: u32, prlar: u32) -> Option<Self> {
+        if prlar & PRLAR_EN == 0 {
+            return None;
+        }
+        Some(Self {
+            base: prbar & !MPU_GRANULE_MASK,
+            limit: prlar | MPU_GRANULE_MASK,
+            access: AccessPermission::from_bits(prbar >> PRBAR_AP_SHIFT),
+            execute_never: prbar & PRBAR_XN != 0,
+            shareability: Shareability::from_bits(prbar >> PRBAR_SH_SHIFT),
+            attr_index: ((prlar >> PRLAR_ATTRINDX_SHIFT) & 0x7) as u8,
+        })
+    }
+}
+
+/// Check a region table: every region valid, no two regions overlapping, and
+/// no more regions than `available`
+///
+/// Region numbers in the returned error are indices into `regions`.
+pub const fn validate_regions(regions: &[MpuRegion], available: u8) -> Result<(), MpuError> {
+    if regions.len() > available as usize {
+        return Err(MpuError::TooManyRegions {
+            requested: regions.len(),
+            available,
+        });
+    }
+    let mut i = 0;
+    while i < regions.len() {
+        if let Err(e) = regions[i].validate(i as u8) {
+            return Err(e);
+        }
+        let mut j = 0;
+        while j < i {
+            if regions[i].overlaps(&regions[j]) {
+                return Err(MpuError::Overlap(j as u8, i as u8));
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+/// Pack eight MAIR attributes into (MAIR0, MAIR1)
+pub const fn mair_pair(attrs: [u8; 8]) -> (u32, u32) {
+    (
+        u32::from_le_bytes([attrs[0], attrs[1], attrs[2], attrs[3]]),
+        u32::from_le_bytes([attrs[4], attrs[5], attrs[6], attrs[7]]),
+    )
+}
+
+/// EL1 MPU
+pub struct Mpu;
+
+impl Mpu {
+    /// Number of EL1 MPU regions implemented (MPUIR.DREGION)
+    pub fn num_regions() -> u8 {
+        let value: u32;
+        unsafe {
+            asm!("mrc p15, 0, {}, c0, c0, 4", out(reg) value);
+        }
+        (value >> 8) as u8
+    }
+
+    /// Program the eight memory attributes referenced by `attr_index`
+    ///
+    /// # Safety
+    /// Changes the memory type of every region using the modified slots.
+    pub unsafe fn set_mair(attrs: [u8; 8]) {
+        let (mair0, mair1) = mair_pair(attrs);
+        unsafe {
+            asm!("mcr p15, 0, {}, c10, c2, 0", in(reg) mair0);
+            asm!("mcr p15, 0, {}, c10, c2, 1", in(reg) mair1);
+            asm!("isb");
+        }
+    }
+
+    /// Read the eight memory attributes from MAIR0/MAIR1
+    pub fn read_mair() -> [u8; 8] {
+        let mair0: u32;
+        let mair1: u32;
+        unsafe {
+            asm!("mrc p15, 0, {}, c10, c2, 0", out(reg) mair0);
+            asm!("mrc p15, 0, {}, c10, c2, 1", out(reg) mair1);
+        }
+        let [a0, a1, a2, a3] = mair0.to_le_bytes();
+        let [a4, a5, a6, a7] = mair1.to_le_bytes();
+        [a0, a1, a2, a3, a4, a5, a6, a7]
+    }
+
+    /// Select region `number` for PRBAR/PRLAR access
+    unsafe fn select(number: u8) {
+        unsafe {
+            asm!("mcr p15, 0, {}, c6, c2, 1", in(reg) number as u32); // PRSELR
+            asm!("isb");
+        }
+    }
+
+    /// Program a single region
+    ///
+    /// The region is checked on its own; overlap with regions already
+    /// programmed is not checked, use [`Mpu::configure`] for that.
+    ///
+    /// # Safety
+    /// Changing a region that covers running code, the stack or live data
+    /// while the MPU is enabled can fault immediately.
+    pub unsafe fn write_region(number: u8, region: &MpuRegion) -> Result<(), MpuError> {
+        if number >= Self::num_regions() {
+            return Err(MpuError::NotImplemented(number));
+        }
+        region.validate(number)?;
+
+        unsafe {
+            Self::select(number);
+            asm!("mcr p15, 0, {}, c6, c3, 1", in(reg) 0u32); // PRLAR: disable while updating
+            asm!("mcr p15, 0, {}, c6, c3, 0", in(reg) region.prbar()); // PRBAR
+            asm!("mcr p15, 0, {}, c6, c3, 1", in(reg) region.prlar()); // PRLAR
+            asm!("dsb sy");
+            asm!("isb");
+        }
+        Ok(())
+    }
+
+    /// Disable region `number`
+    ///
+    /// # Safety
+    /// See [`Mpu::write_region`].
+    pub unsafe fn disable_region(number: u8) {
+        unsafe {
+            Self::select(number);
+            asm!("mcr p15, 0, {}, c6, c3, 1", in(reg) 0u32); // PRLAR
+            asm!("dsb sy");
+            asm!("isb");
+        }
+    }
+
+    /// Read back region `number`; `None` if it is disabled or not implemented
+    pub fn read_region(number: u8) -> Option<MpuRegion> {
+        if number >= Self::num_regions() {
+            return None;
+        }
+        let prbar: u32;
+        let prlar: u32;
+        unsafe {
+            Self::select(number);
+            asm!("mrc p15, 0, {}, c6, c3, 0", out(reg) prbar);
+            asm!("mrc p15, 0, {}, c6, c3, 1", out(reg) prlar);
+        }
+        MpuRegion::from_registers(prbar, prlar)
+    }
+
+    /// Replace the whole region table
+    ///
+    /// `regions[n]` is programmed into region `n`; the remaining implemented
+    /// regions are disabled. Nothing is written unless the table passes
+    /// [`validate_regions`]. The MPU enable state is not changed.
+    ///
+    /// # Safety
+    /// See [`Mpu::write_region`]; normally called before [`Mpu::enable`].
+    pub unsafe fn configure(regions: &[MpuRegion]) -> Result<(), MpuError> {
+        let available = Self::num_regions();
+        validate_regions(regions, available)?;
+
+        for number in 0..available {
+            match regions.get(number as usize) {
+                Some(region) => unsafe { Self::write_region(number, region)? },
+                None => unsafe { Self::disable_region(number) },
+            }
+        }
+        Ok(())
+    }
+
+    /// Enable the MPU
+    ///
+    /// With `background_region`, privileged accesses that hit no region use
+    /// the default memory map instead of faulting; unprivileged accesses
+    /// always fault outside the programmed regions.
+    ///
+    /// # Safety
+    /// The regions must cover the running code, stacks and data, or the next
+    /// access faults.
+    pub unsafe fn enable(background_region: bool) {
+        let mut sctlr = read_sctlr();
+        sctlr |= SCTLR_M;
+        if background_region {
+            sctlr |= SCTLR_BR;
+        } else {
+            sctlr &= !SCTLR_BR;
+        }
+        unsafe {
+            asm!("dsb sy");
+            write_sctlr(sctlr);
+        }
+    }
+
+    /// Disable the MPU; EL1 and EL0 use the default memory map
+    ///
+    /// # Safety
+    /// Removes all EL1/EL0 memory protection.
+    pub unsafe fn disable() {
+        unsafe {
+            asm!("dsb sy");
+            write_sctlr(read_sctlr() & !SCTLR_M);
+        }
+    }
+
+    /// Returns true if the EL1 MPU is enabled (SCTLR.M)
+    pub fn is_enabled() -> bool {
+        read_sctlr() & SCTLR_M != 0
+    }
+}
+
+/// HCR.VM: EL1 and EL0 accesses are checked against the EL2 MPU
+const HCR_VM: u32 = 1 << 0;
+
+/// EL2 MPU
+///
+/// Uses the same [`MpuRegion`] descriptors as the EL1 MPU. At EL2,
+/// [`AccessPermission::PrivReadWrite`] and [`AccessPermission::PrivReadOnly`]
+/// grant access to EL2 only; [`AccessPermission::ReadWrite`] and
+/// [`AccessPermission::ReadOnly`] also admit EL1 and EL0, so regions forming
+/// the EL1 window must use one of those.
+///
+/// Every function must be called in Hyp mode; at EL1 the register accesses
+/// are undefined instructions.
+///
+/// # Example
+/// ```ignore
+/// #[unsafe(no_mangle)]
+/// extern "C" fn el2_init() {
+///     unsafe {
+///         HypMpu::set_mair([MAIR_NORMAL_WRITE_BACK, MAIR_DEVICE_NGNRE, 0, 0, 0, 0, 0, 0]);
+///         HypMpu::configure(&EL1_WINDOW).unwrap();
+///         HypMpu::enable_for_el1();
+///     }
+/// }
+/// ```
+pub struct HypMpu;
+
+impl HypMpu {
+    /// Number of EL2 MPU regions implemented (HMPUIR.REGION)
+    pub fn num_regions() -> u8 {
+        let value: u32;
+        unsafe {
+            asm!("mrc p15, 4, {}, c0, c0, 4", out(reg) value);
+        }
+        value as u8
+    }
+
+    /// Program the eight memory attributes referenced by `attr_index`
+    ///
+    /// # Safety
+    /// Changes the memory type of every region using the modified slots.
+    pub unsafe fn set_mair(attrs: [u8; 8]) {
+        let (hmair0, hmair1) = mair_pair(attrs);
+        unsafe {
+            asm!("mcr p15, 4, {}, c10, c2, 0", in(reg) hmair0);
+            asm!("mcr p15, 4, {}, c10, c2, 1", in(reg) hmair1);
+            asm!("isb");
+        }
+    }
+
+    /// Read the eight memory attributes from HMAIR0/HMAIR1
+    pub fn read_mair() -> [u8; 8] {
+        let hmair0: u32;
+        let hmair1: u32;
+        unsafe {
+            asm!("mrc p15, 4, {}, c10, c2, 0", out(reg) hmair0);
+            asm!("mrc p15, 4, {}, c10, c2, 1", out(reg) hmair1);
+        }
+        let [a0, a1, a2, a3] = hmair0.to_le_bytes();
+        let [a4, a5, a6, a7] = hmair1.to_le_bytes();
+        [a0, a1, a2, a3, a4, a5, a6, a7]
+    }
+
+    /// Select region `number` for HPRBAR/HPRLAR access
+    unsafe fn select(number: u8) {
+        unsafe {
+            asm!("mcr p15, 4, {}, c6, c2, 1", in(reg) number as u32); // HPRSELR
+            asm!("isb");
+        }
+    }
+
+    /// Program a single region
+    ///
+    /// The region is checked on its own; overlap with regions already
+    /// programmed is not checked, use [`HypMpu::configure`] for that.
+    ///
+    /// # Safety
+    /// Changing a region that covers running code, the stack or live data
+    /// while the EL2 MPU is enabled can fault immediately.
+    pub unsafe fn write_region(number: u8, region: &MpuRegion) -> Result<(), MpuError> {
+        if number >= Self::num_regions() {
+            return Err(MpuError::NotImplemented(number));
+        }
+        region.validate(number)?;
+
+        unsafe {
+            Self::select(number);
+            asm!("mcr p15, 4, {}, c6, c3, 1", in(reg) 0u32); // HPRLAR: disable while updating
+            asm!("mcr p15, 4, {}, c6, c3, 0", in(reg) region.prbar()); // HPRBAR
+            asm!("mcr p15, 4, {}, c6, c3, 1", in(reg) region.prlar()); // HPRLAR
+            asm!("dsb sy");
+            asm!("isb");
+        }
+        Ok(())
+    }
+
+    /// Disable region `number`
+    ///
+    /// # Safety
+    /// See [`HypMpu::write_region`].
+    pub unsafe fn disable_region(number: u8) {
+        unsafe {
+            Self::select(number);
+            asm!("mcr p15, 4, {}, c6, c3, 1", in(reg) 0u32); // HPRLAR
+            asm!("dsb sy");
+            asm!("isb");
+        }
+    }
+
+    /// Read back region `number`; `None` if it is disabled or not implemented
+    pub fn read_region(number: u8) -> Option<MpuRegion> {
+        if number >= Self::num_regions() {
+            return None;
+        }
+        let prbar: u32;
+        let prlar: u32;
+        unsafe {
+            Self::select(number);
+            asm!("mrc p15, 4, {}, c6, c3, 0", out(reg) prbar);
+            asm!("mrc p15, 4, {}, c6, c3, 1", out(reg) prlar);
+        }
+        MpuRegion::from_registers(prbar, prlar)
+    }
+
+    /// Replace the whole region table
+    ///
+    /// `regions[n]` is programmed into region `n`; the remaining implemented
+    /// regions are disabled. Nothing is written unless the table passes
+    /// [`validate_regions`]. Neither enable state is changed.
+    ///
+    /// # Safety
+    /// See [`HypMpu::write_region`].
+    pub unsafe fn configure(regions: &[MpuRegion]) -> Result<(), MpuError> {
+        let available = Self::num_regions();
+        validate_regions(regions, available)?;
+
+        for number in 0..available {
+            match regions.get(number as usize) {
+                Some(region) => unsafe { Self::write_region(number, region)? },
+                None => unsafe { Self::disable_region(number) },
+            }
+        }
+        Ok(())
+    }
+
+    /// Enable the EL2 MPU for Hyp mode's own accesses (HSCTLR.M)
+    ///
+    /// With `background_region`, EL2 accesses that hit no region use the
+    /// default memory map instead of faulting.
+    ///
+    /// # Safety
+    /// The regions must cover the running EL2 code, the Hyp stack and data,
+    /// or the next access faults.
+    pub unsafe fn enable(background_region: bool) {
+        let mut hsctlr = read_hsctlr();
+        hsctlr |= SCTLR_M;
+        if background_region {
+            hsctlr |= SCTLR_BR;
+        } else {
+            hsctlr &= !SCTLR_BR;
+        }
+        unsafe {
+            asm!("dsb sy");
+            write_hsctlr(hsctlr);
+        }
+    }
+
+    /// Disable the EL2 MPU for Hyp mode's own accesses
+    ///
+    /// # Safety
+    /// Removes all EL2 memory protection.
+    pub unsafe fn disable() {
+        unsafe {
+            asm!("dsb sy");
+            write_hsctlr(read_hsctlr() & !SCTLR_M);
+        }
+    }
+
+    /// Returns true if the EL2 MPU is enabled for Hyp mode (HSCTLR.M)
+    pub fn is_enabled() -> bool {
+        read_hsctlr() & SCTLR_M != 0
+    }
+
+    /// Confine EL1 and EL0 to the EL2 MPU regions (HCR.VM)
+    ///
+    /// Every EL1/EL0 access must then be permitted by both the EL1 MPU and an
+    /// EL2 region; one that hits no EL2 region is taken to Hyp mode as a
+    /// stage 2 fault and reported to `rust_hyp_trap_handler`.
+    ///
+    /// # Safety
+    /// The EL2 regions must admit EL1 access to the vector table, the code,
+    /// stacks and data the EL1 software will use.
+    pub unsafe fn enable_for_el1() {
+        unsafe {
+            asm!("dsb sy");
+            write_hcr(read_hcr() | HCR_VM);
+        }
+    }
+
+    /// Stop checking EL1 and EL0 accesses against the EL2 MPU
+    ///
+    /// # Safety
+    /// Removes the hypervisor-enforced EL1 memory window.
+    pub unsafe fn disable_for_el1() {
+        unsafe {
+            asm!("dsb sy");
+            write_hcr(read_hcr() & !HCR_VM);
+        }
+    }
+
+    /// Returns true if EL1 and EL0 accesses are checked against the EL2 MPU
+    pub fn is_enabled_for_el1() -> bool {
+        read_hcr() & HCR_VM != 0
+    }
+}
+
+/// Read SCTLR (System Control Register)
+fn read_sctlr() -> u32 {
+    let value: u32;
+    unsafe {
+        asm!("mrc p15, 0, {}, c1, c0, 0", out(reg) value);
+    }
+    value
+}
+
+/// Write SCTLR and synchronize
+unsafe fn write_sctlr(value: u32) {
+    unsafe {
+        asm!("mcr p15, 0, {}, c1, c0, 0", in(reg) value);
+        asm!("isb");
+    }
+}
+
+/// Read HSCTLR (Hyp System Control Register)
+fn read_hsctlr() -> u32 {
+    let value: u32;
+    unsafe {
+        asm!("mrc p15, 4, {}, c1, c0, 0", out(reg) value);
+    }
+    value
+}
+
+/// Write HSCTLR and synchronize
+unsafe fn write_hsctlr(value: u32) {
+    unsafe {
+        asm!("mcr p15, 4, {}, c1, c0, 0", in(reg) value);
+        asm!("isb");
+    }
+}
+
+/// Read HCR (Hyp Configuration Register)
+fn read_hcr() -> u32 {
+    let value: u32;
+    unsafe {
+        asm!("mrc p15, 4, {}, c1, c1, 0", out(reg) value);
+    }
+    value
+}
+
+/// Write HCR and synchronize
+unsafe fn write_hcr(value: u32) {
+    unsafe {
+        asm!("mcr p15, 4, {}, c1, c1, 0", in(reg) value);
+        asm!("isb");
+    }
+}
@@ -0,0 +1,114 @@
+/// Supervisor call (SVC) system-call interface
+///
+/// ABI: the syscall number is the SVC immediate; `svc #0` means the number is
+/// passed in r7 instead (as done by [`syscall`]). Up to four arguments are
+/// passed in r0-r3 and the result is returned in r0.
+///
+/// With the "svc-dispatcher" feature enabled, the runtime provides
+/// `rust_swi_handler` and routes every SVC through [`dispatch_syscall`], which
+/// calls the handler registered for the number with [`register_syscall`].
+/// Unknown numbers return [`SYSCALL_UNKNOWN`].
+use core::arch::asm;
+use core::ptr::read_volatile;
+
+use crate::dispatcher::HandlerTable;
+use crate::exception::ExceptionFrame;
+
+/// Syscall handler, called with r0-r3 of the caller; the result is returned in r0
+pub type SyscallHandler = fn(args: [u32; 4]) -> u32;
+
+/// Number of syscall slots
+pub const MAX_SYSCALLS: usize = 64;
+
+/// Result returned for a syscall number with no registered handler
+pub const SYSCALL_UNKNOWN: u32 = u32::MAX;
+
+static SYSCALLS: HandlerTable<SyscallHandler, MAX_SYSCALLS> = HandlerTable::new();
+
+/// Register a handler for a syscall number
+///
+/// Replaces any previously registered handler.
+///
+/// # Arguments
+/// * `number` - Syscall number (0 to `MAX_SYSCALLS - 1`)
+/// * `handler` - Function called for the syscall
+///
+/// # Returns
+/// `false` if `number` is out of range
+pub fn register_syscall(number: u32, handler: SyscallHandler) -> bool {
+    SYSCALLS.set(number as usize, Some(handler))
+}
+
+/// Remove the handler for a syscall number
+///
+/// # Arguments
+/// * `number` - Syscall number
+pub fn unregister_syscall(number: u32) {
+    SYSCALLS.set(number as usize, None);
+}
+
+/// Decode the syscall number of the SVC that created `frame`
+///
+/// Reads the SVC instruction preceding the return address: the 24-bit
+/// immediate in ARM state, the 8-bit immediate in Thumb state. An immediate
+/// of 0 selects the number held in r7.
+pub fn syscall_number(frame: &ExceptionFrame) -> u32 {
+    let immediate = unsafe {
+        if frame.is_thumb() {
+            read_volatile(frame.lr.wrapping_sub(2) as *const u16) as u32 & 0xFF
+        } else {
+            read_volatile(frame.lr.wrapping_sub(4) as *const u32) & 0x00FF_FFFF
+        }
+    };
+
+    if immediate == 0 {
+        frame.r[7]
+    } else {
+        immediate
+    }
+}
+
+/// Decode and dispatch one SVC, writing the result to the frame's r0
+///
+/// Applications that provide their own `rust_swi_handler` can call this to
+/// reuse the dispatcher.
+pub fn dispatch_syscall(frame: &mut ExceptionFrame) {
+    let number = syscall_number(frame);
+    let args = [frame.r[0], frame.r[1], frame.r[2], frame.r[3]];
+
+    frame.r[0] = match SYSCALLS.get(number as usize) {
+        Some(handler) => handler(args),
+        None => SYSCALL_UNKNOWN,
+    };
+}
+
+/// Invoke a syscall by number (`svc #0` with the number in r7)
+///
+/// # Arguments
+/// * `number` - Syscall number
+/// * `args` - Arguments passed in r0-r3
+///
+/// # Safety
+/// The syscall may have arbitrary effects depending on its handler.
+pub unsafe fn syscall(number: u32, args: [u32; 4]) -> u32 {
+    let result: u32;
+    unsafe {
+        // An SVC taken from SVC mode overwrites LR_svc
+        asm!(
+            "svc #0",
+            inout("r0") args[0] => result,
+            in("r1") args[1],
+            in("r2") args[2],
+            in("r3") args[3],
+            in("r7") number,
+            out("lr") _,
+        );
+    }
+    result
+}
+
+#[cfg(feature = "svc-dispatcher")]
+#[unsafe(no_mangle)]
+pub extern "C" fn rust_swi_handler(frame: &mut ExceptionFrame) {
+    dispatch_syscall(frame);
+}
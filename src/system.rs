@@ -13,3 +13,59 @@ pub unsafe fn read_icc_sre() -> u32 {
     asm!("mrc p15, 0, {}, c12, c12, 5", out(reg) value);
     value
 }
+
+/// Read MPIDR (Multiprocessor Affinity Register)
+/// Aff2 is in bits [23:16], Aff1 (cluster) in [15:8] and Aff0 (core) in [7:0]
+pub fn read_mpidr() -> u32 {
+    let value: u32;
+    unsafe {
+        asm!("mrc p15, 0, {}, c0, c0, 5", out(reg) value);
+    }
+    value
+}
+
+/// Read CPSR (Current Program Status Register)
+/// Mode is in bits [4:0], the F (FIQ mask) bit is 6 and the I (IRQ mask) bit is 7
+pub fn read_cpsr() -> u32 {
+    let value: u32;
+    unsafe {
+        asm!("mrs {}, cpsr", out(reg) value);
+    }
+    value
+}
+
+/// Read DFSR (Data Fault Status Register)
+pub fn read_dfsr() -> u32 {
+    let value: u32;
+    unsafe {
+        asm!("mrc p15, 0, {}, c5, c0, 0", out(reg) value);
+    }
+    value
+}
+
+/// Read DFAR (Data Fault Address Register)
+pub fn read_dfar() -> u32 {
+    let value: u32;
+    unsafe {
+        asm!("mrc p15, 0, {}, c6, c0, 0", out(reg) value);
+    }
+    value
+}
+
+/// Read IFSR (Instruction Fault Status Register)
+pub fn read_ifsr() -> u32 {
+    let value: u32;
+    unsafe {
+        asm!("mrc p15, 0, {}, c5, c0, 1", out(reg) value);
+    }
+    value
+}
+
+/// Read IFAR (Instruction Fault Address Register)
+pub fn read_ifar() -> u32 {
+    let value: u32;
+    unsafe {
+        asm!("mrc p15, 0, {}, c6, c0, 2", out(reg) value);
+    }
+    value
+}
@@ -0,0 +1,150 @@
+/// Tightly coupled memory (ATCM, BTCM, CTCM)
+///
+/// With the "tcm" feature, `_reset` places each TCM at the base address of
+/// the matching `ATCM`/`BTCM`/`CTCM` region in the linker script, enables it
+/// for EL2 and EL1/EL0, zero-fills it so ECC syndromes are valid, and copies
+/// `.tcm_text` into ATCM and `.tcm_data` into BTCM. CTCM is left for the
+/// application to manage.
+///
+/// Use [`tcm_fn!`](crate::tcm_fn) and [`tcm_static!`](crate::tcm_static) to
+/// place hot interrupt handlers and buffers in the TCMs.
+use core::arch::asm;
+
+/// IMP_xTCMREGIONR.EnableEL2: TCM accessible at EL2
+pub const TCM_ENABLE_EL2: u32 = 1 << 0;
+/// IMP_xTCMREGIONR.EnableEL10: TCM accessible at EL1 and EL0
+pub const TCM_ENABLE_EL10: u32 = 1 << 1;
+
+/// IMP_xTCMREGIONR.Size field position and mask (read-only)
+pub(crate) const TCM_SIZE_SHIFT: u32 = 2;
+const TCM_SIZE_MASK: u32 = 0x1F;
+/// IMP_xTCMREGIONR.BaseAddress mask (bits [31:13])
+const TCM_BASE_MASK: u32 = 0xFFFF_E000;
+
+/// One of the three TCM interfaces
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tcm {
+    A,
+    B,
+    C,
+}
+
+/// Decoded IMP_xTCMREGIONR value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TcmRegion {
+    /// Base address of the TCM
+    pub base: u32,
+    /// Implemented size in bytes, 0 if the TCM is not present
+    pub size: u32,
+    /// Accessible at EL2
+    pub enabled_el2: bool,
+    /// Accessible at EL1 and EL0
+    pub enabled_el10: bool,
+}
+
+impl TcmRegion {
+    /// Decode a raw IMP_xTCMREGIONR value
+    pub const fn from_bits(bits: u32) -> Self {
+        let size_field = (bits >> TCM_SIZE_SHIFT) & TCM_SIZE_MASK;
+        Self {
+            base: bits & TCM_BASE_MASK,
+            // 0b00011 = 4KB, 0b00100 = 8KB, ... 0b01011 = 1MB
+            size: if size_field == 0 {
+                0
+            } else {
+                512 << size_field
+            },
+            enabled_el2: bits & TCM_ENABLE_EL2 != 0,
+            enabled_el10: bits & TCM_ENABLE_EL10 != 0,
+        }
+    }
+
+    /// Returns true if `addr` lies inside this TCM
+    pub const fn contains(&self, addr: u32) -> bool {
+        addr >= self.base && addr - self.base < self.size
+    }
+}
+
+/// Read IMP_xTCMREGIONR for the given TCM
+pub fn read_tcm_region(tcm: Tcm) -> TcmRegion {
+    let value: u32;
+    unsafe {
+        match tcm {
+            Tcm::A => asm!("mrc p15, 0, {}, c9, c1, 0", out(reg) value),
+            Tcm::B => asm!("mrc p15, 0, {}, c9, c1, 1", out(reg) value),
+            Tcm::C => asm!("mrc p15, 0, {}, c9, c1, 2", out(reg) value),
+        }
+    }
+    TcmRegion::from_bits(value)
+}
+
+/// Place a function in ATCM (`.tcm_text`)
+///
+/// # Example
+/// ```ignore
+/// cr52_rt::tcm_fn! {
+///     fn timer_isr() {
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// The function is never inlined, so calls from flash or RAM reach the copy in
+/// TCM; the linker inserts a long-branch veneer where needed.
+///
+/// Requires the "tcm" feature; without it the TCMs are never enabled or
+/// loaded, so using the macro is a compile error.
+#[cfg(feature = "tcm")]
+#[macro_export]
+macro_rules! tcm_fn {
+    ($(#[$attr:meta])* $vis:vis fn $($rest:tt)*) => {
+        $(#[$attr])*
+        #[unsafe(link_section = ".tcm_text")]
+        #[inline(never)]
+        $vis fn $($rest)*
+    };
+    ($(#[$attr:meta])* $vis:vis unsafe fn $($rest:tt)*) => {
+        $(#[$attr])*
+        #[unsafe(link_section = ".tcm_text")]
+        #[inline(never)]
+        $vis unsafe fn $($rest)*
+    };
+}
+
+/// Place a static in BTCM (`.tcm_data`)
+///
+/// # Example
+/// ```ignore
+/// cr52_rt::tcm_static! {
+///     static mut RX_BUFFER: [u8; 1024] = [0; 1024];
+/// }
+/// ```
+///
+/// The initial value is copied from its load address at startup.
+///
+/// Requires the "tcm" feature, like [`tcm_fn!`](crate::tcm_fn).
+#[cfg(feature = "tcm")]
+#[macro_export]
+macro_rules! tcm_static {
+    ($(#[$attr:meta])* $vis:vis static $($rest:tt)*) => {
+        $(#[$attr])*
+        #[unsafe(link_section = ".tcm_data")]
+        $vis static $($rest)*
+    };
+}
+
+#[cfg(not(feature = "tcm"))]
+#[macro_export]
+macro_rules! tcm_fn {
+    ($($tt:tt)*) => {
+        compile_error!("tcm_fn! requires the cr52_rt \"tcm\" feature");
+    };
+}
+
+#[cfg(not(feature = "tcm"))]
+#[macro_export]
+macro_rules! tcm_static {
+    ($($tt:tt)*) => {
+        compile_error!("tcm_static! requires the cr52_rt \"tcm\" feature");
+    };
+}